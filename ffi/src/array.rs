@@ -0,0 +1,198 @@
+//! Conversions between `Vec<T>` and C arrays of `T::CType`
+//!
+//! Arrays handed to C are allocated with `g_malloc()` so callers release
+//! them with `g_free()`, after freeing the elements for `transfer full`.
+//!
+//! Arrays returned `transfer none` or `transfer container` are still owned
+//! by Rust once the call returns, so they are [`keep()`]-ed on the object
+//! the method was called on until the next call or until it's finalized.
+
+use glib::prelude::*;
+
+use crate::FfiConvert;
+
+/// Number of elements before the zero terminator of `data`
+///
+/// # Safety
+///
+/// `data` must be `NULL` or point to an array terminated by an all-zero
+/// element
+pub unsafe fn zero_terminated_len<C: Copy + PartialEq>(data: *const C) -> usize {
+    if data.is_null() {
+        return 0;
+    }
+
+    let zero: C = unsafe { std::mem::zeroed() };
+    let mut len = 0;
+    while unsafe { *data.add(len) } != zero {
+        len += 1;
+    }
+    len
+}
+
+/// Converts an array borrowed from the caller (`transfer none`)
+///
+/// # Safety
+///
+/// `data` must be `NULL` or point to `len` valid elements
+pub unsafe fn from_c_none<T: FfiConvert>(data: *const T::CType, len: usize) -> Vec<T> {
+    if data.is_null() {
+        return Vec::new();
+    }
+
+    (0..len)
+        .map(|i| unsafe { T::from_c_borrowed(*data.add(i)) })
+        .collect()
+}
+
+/// Borrows a C array of numbers, such as a `const guint8*` buffer, as a
+/// slice without copying it
+///
+/// # Safety
+///
+/// `data` must be `NULL` or point to `len` valid elements that outlive `'a`
+pub unsafe fn borrow<'a, T>(data: *const T, len: usize) -> &'a [T] {
+    if data.is_null() || len == 0 {
+        return &[];
+    }
+
+    unsafe { std::slice::from_raw_parts(data, len) }
+}
+
+/// Converts an array whose storage is given to Rust while the elements stay
+/// owned by the caller (`transfer container`)
+///
+/// # Safety
+///
+/// `data` must be `NULL` or a `g_malloc()`ed array of `len` valid elements
+pub unsafe fn from_c_container<T: FfiConvert>(data: *mut T::CType, len: usize) -> Vec<T> {
+    let values = unsafe { from_c_none(data, len) };
+    unsafe { glib::ffi::g_free(data as glib::ffi::gpointer) };
+    values
+}
+
+/// Converts an array whose storage and elements are given to Rust
+/// (`transfer full`)
+///
+/// # Safety
+///
+/// `data` must be `NULL` or a `g_malloc()`ed array of `len` valid, owned
+/// elements
+pub unsafe fn from_c_full<T: FfiConvert>(data: *mut T::CType, len: usize) -> Vec<T> {
+    if data.is_null() {
+        return Vec::new();
+    }
+
+    let values = (0..len)
+        .map(|i| unsafe { T::from_c_owned(*data.add(i)) })
+        .collect();
+    unsafe { glib::ffi::g_free(data as glib::ffi::gpointer) };
+    values
+}
+
+/// Transfers `values` to a newly allocated C array (`transfer full`),
+/// returning it with its length
+///
+/// A zero-terminated array gets an extra all-zero element after the values.
+pub fn to_c_full<T: FfiConvert>(values: Vec<T>, zero_terminated: bool) -> (*mut T::CType, usize) {
+    let len = values.len();
+    let n_elements = len + usize::from(zero_terminated);
+    let data = unsafe {
+        glib::ffi::g_malloc0_n(n_elements.max(1), std::mem::size_of::<T::CType>()) as *mut T::CType
+    };
+
+    for (i, value) in values.into_iter().enumerate() {
+        unsafe { data.add(i).write(value.to_c_owned()) };
+    }
+
+    (data, len)
+}
+
+/// Transfers `values` to a C array kept on `object` under `key`, returning
+/// it with its length (`transfer none`)
+pub fn to_c_none<T: FfiConvert + 'static>(
+    object: &glib::Object,
+    key: &str,
+    values: Vec<T>,
+    zero_terminated: bool,
+) -> (*mut T::CType, usize) {
+    let (data, len) = to_c_full(values, zero_terminated);
+    let data = keep(object, key, data, move |data| {
+        drop(unsafe { from_c_full::<T>(data, len) })
+    });
+    (data, len)
+}
+
+/// Transfers `values` to a C array kept on `object` under `key`, returning a
+/// `g_malloc()`ed copy of the array with its length (`transfer container`)
+pub fn to_c_container<T: FfiConvert + 'static>(
+    object: &glib::Object,
+    key: &str,
+    values: Vec<T>,
+    zero_terminated: bool,
+) -> (*mut T::CType, usize) {
+    let (data, len) = to_c_none(object, key, values, zero_terminated);
+    let n_elements = len + usize::from(zero_terminated);
+    let copy = unsafe {
+        let copy = glib::ffi::g_malloc0_n(n_elements.max(1), std::mem::size_of::<T::CType>())
+            as *mut T::CType;
+        std::ptr::copy_nonoverlapping(data, copy, n_elements);
+        copy
+    };
+    (copy, len)
+}
+
+/// A shallow copy of `array`, whose elements stay owned by `array`
+/// (`transfer container`)
+///
+/// # Safety
+///
+/// `array` must be a valid `GPtrArray`
+pub unsafe fn ptr_array_copy(array: *mut glib::ffi::GPtrArray) -> *mut glib::ffi::GPtrArray {
+    unsafe {
+        let len = (*array).len;
+        let copy = glib::ffi::g_ptr_array_sized_new(len);
+        for i in 0..len as usize {
+            glib::ffi::g_ptr_array_add(copy, *(*array).pdata.add(i));
+        }
+        copy
+    }
+}
+
+/// A value kept on an object, released with `free` when replaced or when the
+/// object is finalized
+struct Kept<P: Copy, F: FnOnce(P)> {
+    value: P,
+    free: Option<F>,
+}
+
+impl<P: Copy, F: FnOnce(P)> Drop for Kept<P, F> {
+    fn drop(&mut self) {
+        if let Some(free) = self.free.take() {
+            free(self.value);
+        }
+    }
+}
+
+/// Keeps the `transfer full` C value `value` on `object` under `key`,
+/// releasing the value kept there by the previous call, and returns it
+///
+/// C callers may use `value` until they call the method again or drop
+/// their reference to `object`.
+pub fn keep<P, F>(object: &glib::Object, key: &str, value: P, free: F) -> P
+where
+    P: Copy + 'static,
+    F: FnOnce(P) + 'static,
+{
+    let key = format!("gobject-ffi-{}", key);
+    unsafe {
+        object.set_data(
+            &key,
+            Kept {
+                value,
+                free: Some(free),
+            },
+        );
+    }
+    value
+}
@@ -0,0 +1,71 @@
+//! Closure parameters
+//!
+//! C passes a closure as a function pointer and a `user_data` pointer handed
+//! back to every call. Closures Rust keeps after the call returns also come
+//! with a `GDestroyNotify`, called on `user_data` once Rust drops them.
+//!
+//! Nothing tells C callers that their `user_data` may be used from other
+//! threads, so [`UserData`] is neither `Send` nor `Sync`. Closure parameters
+//! whose Rust bound asks for `Send` or `Sync` are marked
+//! `#[ffi(thread_safe)]`, which documents the requirement in the header and
+//! wraps the data in [`ThreadSafeUserData`].
+
+use glib::ffi::{GDestroyNotify, gpointer};
+
+/// The `user_data` of a C callback, released with its `GDestroyNotify` when
+/// dropped
+pub struct UserData {
+    data: gpointer,
+    destroy: GDestroyNotify,
+}
+
+impl UserData {
+    /// Takes `data` over, `destroy` is `None` for data that stays owned by
+    /// the caller
+    pub fn new(data: gpointer, destroy: GDestroyNotify) -> Self {
+        UserData { data, destroy }
+    }
+
+    /// The pointer passed back to the callback
+    pub fn as_ptr(&self) -> gpointer {
+        self.data
+    }
+
+    /// Lets the data, and the closure holding it, move to other threads
+    ///
+    /// # Safety
+    ///
+    /// The caller must have been told that `data`, its callback and its
+    /// `GDestroyNotify` may be used from any thread.
+    pub unsafe fn assume_thread_safe(self) -> ThreadSafeUserData {
+        ThreadSafeUserData(self)
+    }
+}
+
+impl Drop for UserData {
+    fn drop(&mut self) {
+        if let Some(destroy) = self.destroy {
+            unsafe { destroy(self.data) }
+        }
+    }
+}
+
+/// [`UserData`] the caller documented as usable from any thread, for
+/// `#[ffi(thread_safe)]` closures
+pub struct ThreadSafeUserData(UserData);
+
+// Only built by `UserData::assume_thread_safe()`
+unsafe impl Send for ThreadSafeUserData {}
+unsafe impl Sync for ThreadSafeUserData {}
+
+impl ThreadSafeUserData {
+    /// The pointer passed back to the callback
+    pub fn as_ptr(&self) -> gpointer {
+        self.0.as_ptr()
+    }
+}
+
+/// Rejects a `NULL` callback for a closure that isn't an `Option`
+pub fn required<F>(func: Option<F>, param: &str) -> Result<F, glib::Error> {
+    func.ok_or_else(|| crate::strings::null_error(param))
+}
@@ -0,0 +1,264 @@
+//! Conversions between Rust maps and `GHashTable`
+//!
+//! Tables handed to C own their keys and values through the destroy
+//! functions of [`HashTableElement`], so callers only unref the table.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    os::raw::c_char,
+};
+
+use glib::ffi::{GDestroyNotify, GEqualFunc, GHashFunc, GHashTable, gconstpointer, gpointer};
+
+use crate::FfiConvert;
+
+/// Types stored in a `GHashTable` slot, either as keys or as values
+pub trait HashTableElement: FfiConvert {
+    /// Packs the C value into a table slot
+    fn to_pointer(value: Self::CType) -> gpointer;
+
+    /// Unpacks the C value from a table slot
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been created by [`HashTableElement::to_pointer`] or by
+    /// C code following the same convention
+    unsafe fn from_pointer(ptr: gconstpointer) -> Self::CType;
+
+    /// Releases a value owned by the table
+    fn destroy_func() -> GDestroyNotify;
+}
+
+/// Types usable as `GHashTable` keys
+pub trait HashTableKey: HashTableElement {
+    /// Hashes a key slot
+    fn hash_func() -> GHashFunc;
+
+    /// Compares two key slots
+    fn equal_func() -> GEqualFunc;
+}
+
+macro_rules! impl_hash_table_string {
+    ($rust_type:ty) => {
+        impl HashTableElement for $rust_type {
+            fn to_pointer(value: Self::CType) -> gpointer {
+                value as gpointer
+            }
+
+            unsafe fn from_pointer(ptr: gconstpointer) -> Self::CType {
+                ptr as *mut c_char
+            }
+
+            fn destroy_func() -> GDestroyNotify {
+                Some(glib::ffi::g_free)
+            }
+        }
+
+        impl HashTableKey for $rust_type {
+            fn hash_func() -> GHashFunc {
+                Some(glib::ffi::g_str_hash)
+            }
+
+            fn equal_func() -> GEqualFunc {
+                Some(glib::ffi::g_str_equal)
+            }
+        }
+    };
+}
+
+impl_hash_table_string!(String);
+impl_hash_table_string!(glib::GString);
+
+// Integers are stored in the pointer itself, as with `GINT_TO_POINTER()`
+macro_rules! impl_hash_table_integer {
+    ($rust_type:ty) => {
+        impl HashTableElement for $rust_type {
+            fn to_pointer(value: Self::CType) -> gpointer {
+                value as isize as gpointer
+            }
+
+            unsafe fn from_pointer(ptr: gconstpointer) -> Self::CType {
+                ptr as isize as Self::CType
+            }
+
+            fn destroy_func() -> GDestroyNotify {
+                None
+            }
+        }
+
+        impl HashTableKey for $rust_type {
+            fn hash_func() -> GHashFunc {
+                Some(glib::ffi::g_direct_hash)
+            }
+
+            fn equal_func() -> GEqualFunc {
+                Some(glib::ffi::g_direct_equal)
+            }
+        }
+    };
+}
+
+impl_hash_table_integer!(i8);
+impl_hash_table_integer!(u8);
+impl_hash_table_integer!(i16);
+impl_hash_table_integer!(u16);
+impl_hash_table_integer!(i32);
+impl_hash_table_integer!(u32);
+
+impl HashTableElement for bool {
+    fn to_pointer(value: Self::CType) -> gpointer {
+        value as isize as gpointer
+    }
+
+    unsafe fn from_pointer(ptr: gconstpointer) -> Self::CType {
+        ptr as isize as Self::CType
+    }
+
+    fn destroy_func() -> GDestroyNotify {
+        None
+    }
+}
+
+unsafe extern "C" fn variant_unref(ptr: gpointer) {
+    unsafe { glib::ffi::g_variant_unref(ptr as *mut glib::ffi::GVariant) }
+}
+
+impl HashTableElement for glib::Variant {
+    fn to_pointer(value: Self::CType) -> gpointer {
+        value as gpointer
+    }
+
+    unsafe fn from_pointer(ptr: gconstpointer) -> Self::CType {
+        ptr as *mut glib::ffi::GVariant
+    }
+
+    fn destroy_func() -> GDestroyNotify {
+        Some(variant_unref)
+    }
+}
+
+impl HashTableKey for glib::Variant {
+    fn hash_func() -> GHashFunc {
+        Some(glib::ffi::g_variant_hash)
+    }
+
+    fn equal_func() -> GEqualFunc {
+        Some(glib::ffi::g_variant_equal)
+    }
+}
+
+/// Collects the entries of a table borrowed from C
+///
+/// # Safety
+///
+/// `table` must be `NULL` or a valid `GHashTable` whose slots follow the
+/// [`HashTableElement`] conventions of `K` and `V`
+unsafe fn from_hash_table<K, V, M>(table: *mut GHashTable) -> M
+where
+    K: HashTableKey,
+    V: HashTableElement,
+    M: FromIterator<(K, V)> + Default,
+{
+    if table.is_null() {
+        return M::default();
+    }
+
+    let mut entries = Vec::new();
+    unsafe {
+        let mut iter = std::mem::MaybeUninit::uninit();
+        glib::ffi::g_hash_table_iter_init(iter.as_mut_ptr(), table);
+        let mut key = std::ptr::null_mut();
+        let mut value = std::ptr::null_mut();
+        while glib::ffi::g_hash_table_iter_next(iter.as_mut_ptr(), &mut key, &mut value)
+            != glib::ffi::GFALSE
+        {
+            entries.push((
+                K::from_c_borrowed(K::from_pointer(key)),
+                V::from_c_borrowed(V::from_pointer(value)),
+            ));
+        }
+    }
+    entries.into_iter().collect()
+}
+
+/// Creates a table owning the converted entries
+fn to_hash_table<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> *mut GHashTable
+where
+    K: HashTableKey,
+    V: HashTableElement,
+{
+    unsafe {
+        let table = glib::ffi::g_hash_table_new_full(
+            K::hash_func(),
+            K::equal_func(),
+            K::destroy_func(),
+            V::destroy_func(),
+        );
+        for (key, value) in entries {
+            glib::ffi::g_hash_table_insert(
+                table,
+                K::to_pointer(key.to_c_owned()),
+                V::to_pointer(value.to_c_owned()),
+            );
+        }
+        table
+    }
+}
+
+impl<K, V, S> FfiConvert for HashMap<K, V, S>
+where
+    K: HashTableKey + Eq + Hash,
+    V: HashTableElement,
+    S: BuildHasher + Default,
+{
+    type CType = *mut GHashTable;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_hash_table(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        let map = unsafe { from_hash_table(value) };
+        if !value.is_null() {
+            unsafe { glib::ffi::g_hash_table_unref(value) };
+        }
+        map
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        to_hash_table(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
+impl<K, V> FfiConvert for BTreeMap<K, V>
+where
+    K: HashTableKey + Ord,
+    V: HashTableElement,
+{
+    type CType = *mut GHashTable;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_hash_table(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        let map = unsafe { from_hash_table(value) };
+        if !value.is_null() {
+            unsafe { glib::ffi::g_hash_table_unref(value) };
+        }
+        map
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        to_hash_table(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
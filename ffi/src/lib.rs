@@ -1,10 +1,20 @@
 #![doc = include_str!("../../README.md")]
 
-use std::{os::raw::c_char, path::PathBuf};
+use std::{ffi::OsString, os::raw::c_char, path::PathBuf};
 
 use glib::translate::*;
 pub use gobject_macros::{c_return_type, ffi_impl};
 
+pub mod array;
+pub mod callback;
+pub mod hash_table;
+pub mod manifest;
+pub mod property;
+pub mod signal;
+pub mod strings;
+pub mod time;
+pub mod variant;
+
 /// Trait for types that can be converted to/from C FFI representations
 ///
 /// # Example
@@ -54,11 +64,106 @@ pub trait FfiConvert: Sized {
     /// `Self::CType`
     unsafe fn from_c_borrowed(value: Self::CType) -> Self;
 
+    /// Convert from a C value whose ownership is transferred to Rust, as for
+    /// `(inout)` parameters
+    ///
+    /// The default implementation is only correct for types without owned C
+    /// resources; pointer types must override it to release the C value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `value` is a valid, owned representation
+    /// of `Self::CType`
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { Self::from_c_borrowed(value) }
+    }
+
     /// Transfer ownership to C as return value
     fn to_c_owned(self) -> Self::CType;
 
     /// Error value returned when Result<T, E> fails
     fn c_error_value() -> Self::CType;
+
+    /// Whether `value` can't be converted, such as `NULL` for a pointer
+    /// that must not be `NULL`. Pointer types override this, as the default
+    /// accepts every value
+    fn c_is_null(value: Self::CType) -> bool {
+        let _ = value;
+        false
+    }
+
+    /// Checked [`from_c_borrowed`](Self::from_c_borrowed) for a parameter
+    /// named `param`, failing with a `G_CONVERT_ERROR` instead of panicking
+    /// on values such as `NULL`
+    ///
+    /// # Safety
+    ///
+    /// `value` must be `NULL` or a valid representation of `Self::CType`
+    unsafe fn try_from_c_borrowed(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if Self::c_is_null(value) {
+            return Err(strings::null_error(param));
+        }
+        Ok(unsafe { Self::from_c_borrowed(value) })
+    }
+
+    /// Checked [`from_c_owned`](Self::from_c_owned), see
+    /// [`try_from_c_borrowed`](Self::try_from_c_borrowed)
+    ///
+    /// # Safety
+    ///
+    /// `value` must be `NULL` or a valid, owned representation of
+    /// `Self::CType`
+    unsafe fn try_from_c_owned(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if Self::c_is_null(value) {
+            return Err(strings::null_error(param));
+        }
+        Ok(unsafe { Self::from_c_owned(value) })
+    }
+}
+
+/// Rust structs laid out like the C struct declared for them, which
+/// `#[ffi_impl(ty = "struct")]` implements once it checked the layout
+///
+/// # Safety
+///
+/// The type must be `#[repr(C)]` with the fields of its C declaration
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no C layout for `(out caller-allocates)`",
+    note = "declare it with `#[ffi_impl(ty = \"struct\", fields(...))]`"
+)]
+pub unsafe trait FfiRecord: Copy {}
+
+/// Fails to compile unless C can allocate `T` for Rust to fill in, as
+/// `(out caller-allocates)` parameters require
+pub fn assert_record<T: FfiRecord>() {}
+
+/// Reads the value of an `(inout)` parameter without taking it over, so the
+/// caller's value stays valid if the call fails
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a valid representation of `T::CType`
+pub unsafe fn inout_from_c<T: FfiConvert>(
+    ptr: *const T::CType,
+    param: &str,
+) -> Result<T, glib::Error> {
+    if ptr.is_null() {
+        return Err(strings::null_error(param));
+    }
+    unsafe { T::try_from_c_borrowed(*ptr, param) }
+}
+
+/// Replaces the value of an `(inout)` parameter once the call succeeded,
+/// releasing the caller's value it takes over
+///
+/// # Safety
+///
+/// `ptr` must point to the valid, owned value read by [`inout_from_c`]
+pub unsafe fn inout_to_c<T: FfiConvert>(ptr: *mut T::CType, value: T) {
+    unsafe {
+        drop(T::from_c_owned(*ptr));
+        *ptr = value.to_c_owned();
+    }
 }
 
 impl FfiConvert for bool {
@@ -80,10 +185,18 @@ impl FfiConvert for bool {
 impl FfiConvert for String {
     type CType = *mut c_char;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         ToGlibPtr::to_glib_full(&self)
     }
@@ -96,10 +209,42 @@ impl FfiConvert for String {
 impl FfiConvert for PathBuf {
     type CType = *mut c_char;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_glib_none(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        ToGlibPtr::to_glib_full(&self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
+impl FfiConvert for OsString {
+    type CType = *mut c_char;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         ToGlibPtr::to_glib_full(&self)
     }
@@ -109,14 +254,150 @@ impl FfiConvert for PathBuf {
     }
 }
 
+/// Takes over the descriptor (`transfer full`); borrowed descriptors are
+/// duplicated
+///
+/// Duplicating can fail, so `#[ffi_impl]` only borrows descriptors through
+/// [`FfiConvert::try_from_c_borrowed`] and rejects arrays of `OwnedFd` that
+/// aren't `transfer full`. [`FfiConvert::from_c_borrowed`] aborts instead
+/// of unwinding into C when it fails.
+#[cfg(unix)]
+impl FfiConvert for std::os::fd::OwnedFd {
+    type CType = std::os::fd::RawFd;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        match unsafe { Self::try_from_c_borrowed(value, "fd") } {
+            Ok(fd) => fd,
+            Err(err) => {
+                strings::critical("from_c_borrowed", &err);
+                std::process::abort()
+            }
+        }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { std::os::fd::FromRawFd::from_raw_fd(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        std::os::fd::IntoRawFd::into_raw_fd(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        -1
+    }
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value < 0
+    }
+
+    unsafe fn try_from_c_borrowed(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if value < 0 {
+            return Err(bad_fd_error(param));
+        }
+        unsafe { std::os::fd::BorrowedFd::borrow_raw(value) }
+            .try_clone_to_owned()
+            .map_err(|err| {
+                let code = unsafe {
+                    glib::translate::from_glib(glib::ffi::g_file_error_from_errno(
+                        err.raw_os_error().unwrap_or(0),
+                    ))
+                };
+                glib::Error::new::<glib::FileError>(
+                    code,
+                    &format!("Failed to duplicate `{param}`: {err}"),
+                )
+            })
+    }
+
+    unsafe fn try_from_c_owned(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if value < 0 {
+            return Err(bad_fd_error(param));
+        }
+        Ok(unsafe { Self::from_c_owned(value) })
+    }
+}
+
+/// The descriptor stays owned by the side passing it (`transfer none`)
+#[cfg(unix)]
+impl FfiConvert for std::os::fd::BorrowedFd<'_> {
+    type CType = std::os::fd::RawFd;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { std::os::fd::BorrowedFd::borrow_raw(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        std::os::fd::AsRawFd::as_raw_fd(&self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        -1
+    }
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value < 0
+    }
+
+    unsafe fn try_from_c_borrowed(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if value < 0 {
+            return Err(bad_fd_error(param));
+        }
+        Ok(unsafe { Self::from_c_borrowed(value) })
+    }
+}
+
+/// `G_FILE_ERROR_BADF` for a descriptor parameter passed as -1
+#[cfg(unix)]
+fn bad_fd_error(param: &str) -> glib::Error {
+    glib::Error::new(
+        glib::FileError::Badf,
+        &format!("`{param}` is not a valid file descriptor"),
+    )
+}
+
+#[cfg(unix)]
+impl FfiConvert for gio::UnixFDList {
+    type CType = *mut gio::ffi::GUnixFDList;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_glib_none(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        IntoGlibPtr::into_glib_ptr(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
 impl FfiConvert for Vec<u8> {
     type CType = *mut glib::ffi::GBytes;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         let bytes: glib::Bytes = unsafe { from_glib_none(value) };
         bytes.to_vec()
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        let bytes: glib::Bytes = unsafe { from_glib_full(value) };
+        bytes.to_vec()
+    }
+
     fn to_c_owned(self) -> Self::CType {
         let bytes = glib::Bytes::from_owned(self);
         IntoGlibPtr::into_glib_ptr(bytes)
@@ -171,10 +452,18 @@ impl FfiConvert for () {
 impl FfiConvert for glib::Bytes {
     type CType = *mut glib::ffi::GBytes;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         ToGlibPtr::to_glib_full(&self)
     }
@@ -187,10 +476,18 @@ impl FfiConvert for glib::Bytes {
 impl FfiConvert for glib::Variant {
     type CType = *mut glib::ffi::GVariant;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         ToGlibPtr::to_glib_full(&self)
     }
@@ -200,13 +497,47 @@ impl FfiConvert for glib::Variant {
     }
 }
 
+/// Returned values are allocated copies, freed with
+/// `g_boxed_free (G_TYPE_VALUE, value)`
+impl FfiConvert for glib::Value {
+    type CType = *mut glib::gobject_ffi::GValue;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_glib_none(value as *const glib::gobject_ffi::GValue) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        IntoGlibPtr::<*mut glib::gobject_ffi::GValue>::into_glib_ptr(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
 impl FfiConvert for glib::GString {
     type CType = *mut c_char;
 
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         ToGlibPtr::to_glib_full(&self)
     }
@@ -216,15 +547,49 @@ impl FfiConvert for glib::GString {
     }
 }
 
+/// `GStrv`: a `NULL`-terminated array of strings
 impl FfiConvert for Vec<String> {
-    type CType = *mut glib::ffi::GList;
+    type CType = *mut *mut c_char;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
 
     unsafe fn from_c_borrowed(value: Self::CType) -> Self {
         unsafe { FromGlibPtrContainer::from_glib_none(value) }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { FromGlibPtrContainer::from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        glib::StrV::from_iter(self.into_iter().map(glib::GString::from)).into_raw()
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
+/// `GStrv`: a `NULL`-terminated array of strings
+impl FfiConvert for Vec<glib::GString> {
+    type CType = *mut *mut c_char;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { FromGlibPtrContainer::from_glib_none(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { FromGlibPtrContainer::from_glib_full(value) }
+    }
+
     fn to_c_owned(self) -> Self::CType {
-        glib::translate::ToGlibContainerFromSlice::to_glib_full_from_slice(&self)
+        glib::StrV::from_iter(self).into_raw()
     }
 
     fn c_error_value() -> Self::CType {
@@ -232,6 +597,37 @@ impl FfiConvert for Vec<String> {
     }
 }
 
+impl FfiConvert for glib::StrV {
+    type CType = *mut *mut c_char;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { glib::StrV::from_glib_none(value as *const *const c_char) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { glib::StrV::from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        self.into_raw()
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
+
+/// `None` is represented by `T::c_error_value()`.
+///
+/// For pointer types this is `NULL`. `#[ffi_impl]` only uses this sentinel
+/// for an `Option<primitive>` parameter marked `#[ffi(sentinel)]`, or for
+/// the return value of a method marked `#[ffi(sentinel)]`; otherwise it
+/// passes a nullable pointer or an out parameter instead. Marking the
+/// method leaves its parameters as nullable pointers.
 impl<T: FfiConvert> FfiConvert for Option<T>
 where
     T::CType: PartialEq,
@@ -246,6 +642,14 @@ where
         }
     }
 
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        if value == T::c_error_value() {
+            None
+        } else {
+            Some(unsafe { T::from_c_owned(value) })
+        }
+    }
+
     fn to_c_owned(self) -> Self::CType {
         match self {
             Some(val) => T::to_c_owned(val),
@@ -256,4 +660,20 @@ where
     fn c_error_value() -> Self::CType {
         T::c_error_value()
     }
+
+    unsafe fn try_from_c_borrowed(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if value == T::c_error_value() {
+            Ok(None)
+        } else {
+            unsafe { T::try_from_c_borrowed(value, param) }.map(Some)
+        }
+    }
+
+    unsafe fn try_from_c_owned(value: Self::CType, param: &str) -> Result<Self, glib::Error> {
+        if value == T::c_error_value() {
+            Ok(None)
+        } else {
+            unsafe { T::try_from_c_owned(value, param) }.map(Some)
+        }
+    }
 }
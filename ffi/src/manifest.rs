@@ -0,0 +1,171 @@
+//! Type hierarchy of exported object and interface types
+//!
+//! `#[ffi_impl]` can't see `ObjectSubclass::ParentType` or `type Interfaces`
+//! while expanding, so the parent and interfaces are read from the registered
+//! `GType` instead. A build step such as a small binary or test collects
+//! [`TypeManifest::of`] for each exported type and writes the GIR elements
+//! and manifest entries next to the generated headers:
+//!
+//! ```ignore
+//! let types = [TypeManifest::of::<Shape>(), TypeManifest::of::<Square>()];
+//! gobject_ffi::manifest::write_key_file("shapes.manifest", &types)?;
+//! ```
+
+use std::{fmt::Write as _, path::Path};
+
+use glib::prelude::*;
+
+/// Implemented by `#[ffi_impl]` for object and interface types
+pub trait FfiManifest: StaticType {
+    /// Name of the type in the generated header
+    const C_TYPE_NAME: &'static str;
+    /// Name of the exported `<prefix>_get_type` function
+    const GET_TYPE: &'static str;
+}
+
+/// Parent and interfaces of an exported type, as registered with GObject
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeManifest {
+    pub c_type_name: &'static str,
+    pub get_type: &'static str,
+    /// `GType` name, from `ObjectSubclass::NAME` or `ObjectInterface::NAME`
+    pub type_name: String,
+    pub is_interface: bool,
+    /// GIR name of the parent class, `None` for interfaces
+    pub parent: Option<String>,
+    /// GIR names of the interfaces the type adds to its parent, or of the
+    /// prerequisites of an interface
+    pub implements: Vec<String>,
+}
+
+impl TypeManifest {
+    /// Registers `T` if needed and reads its hierarchy
+    pub fn of<T: FfiManifest>() -> Self {
+        let type_ = T::static_type();
+        let is_interface = type_.is_a(glib::Type::INTERFACE);
+        let (parent, implements) = if is_interface {
+            let prerequisites = type_.interface_prerequisites();
+            (None, prerequisites.iter().copied().map(gir_name).collect())
+        } else {
+            let parent = type_.parent();
+            // Interfaces inherited from the parent are listed on the parent
+            let implements = type_
+                .interfaces()
+                .iter()
+                .copied()
+                .filter(|interface| !parent.is_some_and(|parent| parent.is_a(*interface)))
+                .map(gir_name)
+                .collect();
+            (parent.map(gir_name), implements)
+        };
+
+        TypeManifest {
+            c_type_name: T::C_TYPE_NAME,
+            get_type: T::GET_TYPE,
+            type_name: type_.name().to_string(),
+            is_interface,
+            parent,
+            implements,
+        }
+    }
+
+    /// `<class>` or `<interface>` element, with `<implements>` or
+    /// `<prerequisite>` children, to merge into the namespace of a GIR file
+    pub fn to_gir(&self) -> String {
+        let (element, child) = if self.is_interface {
+            ("interface", "prerequisite")
+        } else {
+            ("class", "implements")
+        };
+        let mut gir = format!(
+            "<{} name=\"{}\" c:type=\"{}\"",
+            element, self.c_type_name, self.c_type_name
+        );
+        if let Some(ref parent) = self.parent {
+            let _ = write!(gir, " parent=\"{}\"", parent);
+        }
+        let _ = write!(
+            gir,
+            " glib:type-name=\"{}\" glib:get-type=\"{}\"",
+            self.type_name, self.get_type
+        );
+        if self.implements.is_empty() {
+            gir.push_str("/>\n");
+            return gir;
+        }
+        gir.push_str(">\n");
+        for interface in &self.implements {
+            let _ = writeln!(gir, "  <{} name=\"{}\"/>", child, interface);
+        }
+        let _ = writeln!(gir, "</{}>", element);
+        gir
+    }
+
+    /// `GKeyFile` group named after the C type
+    pub fn to_key_file(&self) -> String {
+        let mut group = format!(
+            "[{}]\nkind={}\ntype-name={}\nget-type={}\n",
+            self.c_type_name,
+            if self.is_interface {
+                "interface"
+            } else {
+                "class"
+            },
+            self.type_name,
+            self.get_type
+        );
+        if let Some(ref parent) = self.parent {
+            let _ = writeln!(group, "parent={}", parent);
+        }
+        if !self.implements.is_empty() {
+            let key = if self.is_interface {
+                "prerequisites"
+            } else {
+                "implements"
+            };
+            let _ = writeln!(group, "{}={};", key, self.implements.join(";"));
+        }
+        group
+    }
+}
+
+/// Writes the manifest entries of `types` as a `GKeyFile`
+pub fn write_key_file(path: impl AsRef<Path>, types: &[TypeManifest]) -> std::io::Result<()> {
+    let groups = types
+        .iter()
+        .map(TypeManifest::to_key_file)
+        .collect::<Vec<_>>();
+    std::fs::write(path, groups.join("\n"))
+}
+
+/// GIR name of a registered type: namespaced for the GNOME libraries the
+/// generated headers know about, the `GType` name otherwise
+fn gir_name(type_: glib::Type) -> String {
+    const GOBJECT_TYPES: &[&str] = &[
+        "GObject",
+        "GInitiallyUnowned",
+        "GBinding",
+        "GBindingGroup",
+        "GSignalGroup",
+        "GTypeModule",
+        "GTypePlugin",
+    ];
+    const NAMESPACES: &[&str] = &["Gtk", "Gdk", "Adw", "Gst", "Pango", "Soup"];
+
+    let name = type_.name();
+    if GOBJECT_TYPES.contains(&name) {
+        return format!("GObject.{}", &name[1..]);
+    }
+    for namespace in NAMESPACES {
+        if let Some(rest) = name.strip_prefix(namespace) {
+            if rest.starts_with(char::is_uppercase) {
+                return format!("{}.{}", namespace, rest);
+            }
+        }
+    }
+    // GLib itself has no classes or interfaces, so the rest of `G` is GIO
+    match name.strip_prefix('G') {
+        Some(rest) if rest.starts_with(char::is_uppercase) => format!("Gio.{}", rest),
+        _ => name.to_string(),
+    }
+}
@@ -0,0 +1,48 @@
+//! Typed property accessors for the `get_`/`set_` exports of declared
+//! properties
+//!
+//! These go through `g_object_get_property()` and `g_object_set_property()`
+//! so that an unknown property, a mismatched type or a value outside the
+//! `ParamSpec`'s range is reported with a warning and ignored, exactly as
+//! with `g_object_get()` and `g_object_set()`. `ObjectExt::property()` and
+//! `ObjectExt::set_property()` panic instead, which would abort the process
+//! from a C call.
+
+use std::ffi::CString;
+
+use glib::{prelude::*, translate::*, value::FromValue};
+
+/// Reads property `name` of `object` as a `T`, or returns `None` once GLib
+/// warned that it can't
+pub fn get<T>(object: &glib::Object, name: &str) -> Option<T>
+where
+    T: StaticType + for<'a> FromValue<'a>,
+{
+    let name = CString::new(name).ok()?;
+    let mut value = glib::Value::from_type(T::static_type());
+    unsafe {
+        glib::gobject_ffi::g_object_get_property(
+            object.as_ptr(),
+            name.as_ptr(),
+            value.to_glib_none_mut().0,
+        );
+    }
+    value.get::<T>().ok()
+}
+
+/// Sets property `name` of `object` to `value`, which GLib validates
+/// against the property's `ParamSpec` and ignores with a warning when it
+/// doesn't fit
+pub fn set<T: ToValue>(object: &glib::Object, name: &str, value: T) {
+    let Ok(name) = CString::new(name) else {
+        return;
+    };
+    let value = value.to_value();
+    unsafe {
+        glib::gobject_ffi::g_object_set_property(
+            object.as_ptr(),
+            name.as_ptr(),
+            value.to_glib_none().0,
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Connecting C handlers to signals
+//!
+//! Handlers are plain C functions invoked through a `GCClosure`, exactly as
+//! with `g_signal_connect_data()`. The signal is looked up first so that a
+//! handler typedef out of sync with the signal's parameter or return types
+//! is rejected instead of being called with the wrong arguments.
+
+use std::{ffi::CString, os::raw::c_ulong};
+
+use glib::{
+    ffi::gpointer,
+    gobject_ffi::{GCallback, GClosureNotify, GObject},
+    translate::*,
+};
+
+/// Flag `g_signal_query()` leaves in the types of parameters declared with
+/// `G_SIGNAL_TYPE_STATIC_SCOPE`, which gobject-sys doesn't export
+const G_SIGNAL_TYPE_STATIC_SCOPE: glib::ffi::GType = 1;
+
+fn signal_error(message: String) -> glib::Error {
+    glib::Error::new(glib::ConvertError::Failed, &message)
+}
+
+/// Types of the values a handler takes and returns, as `GType`s
+pub struct HandlerSignature<'a> {
+    pub param_types: &'a [glib::Type],
+    /// `G_TYPE_NONE` for handlers returning nothing
+    pub return_type: glib::Type,
+}
+
+/// Connects `handler`, a C function taking the instance, the arguments of
+/// `signature` and `data`, to `signal` on `instance`. Returns the handler
+/// ID, or 0 after logging a critical for `function` if it can't be connected
+///
+/// # Safety
+///
+/// `instance` must be a valid `GObject` and `handler` must have the C
+/// signature of the signal's handlers
+pub unsafe fn connect(
+    function: &str,
+    instance: *mut GObject,
+    signal: &str,
+    signature: HandlerSignature<'_>,
+    handler: GCallback,
+    data: gpointer,
+    destroy_data: GClosureNotify,
+) -> c_ulong {
+    let result = unsafe { lookup(instance, signal, &signature) };
+    if let Err(err) = result {
+        crate::strings::critical(function, &err);
+        return 0;
+    }
+    if handler.is_none() {
+        crate::strings::critical(function, &crate::strings::null_error("handler"));
+        return 0;
+    }
+
+    let signal = CString::new(signal).expect("signal names have no nul bytes");
+    unsafe {
+        glib::gobject_ffi::g_signal_connect_data(
+            instance,
+            signal.as_ptr(),
+            handler,
+            data,
+            destroy_data,
+            0,
+        )
+    }
+}
+
+/// Checks that `instance` has `signal`, whose values can be passed to and
+/// returned from a handler of `signature`
+unsafe fn lookup(
+    instance: *mut GObject,
+    signal: &str,
+    signature: &HandlerSignature<'_>,
+) -> Result<(), glib::Error> {
+    let HandlerSignature {
+        param_types,
+        return_type,
+    } = *signature;
+    if instance.is_null() {
+        return Err(crate::strings::null_error("self"));
+    }
+
+    let type_: glib::Type = unsafe { from_glib((*(*instance).g_type_instance.g_class).g_type) };
+    let signal_c = CString::new(signal).expect("signal names have no nul bytes");
+    let id = unsafe { glib::gobject_ffi::g_signal_lookup(signal_c.as_ptr(), type_.into_glib()) };
+    if id == 0 {
+        return Err(signal_error(format!(
+            "`{}` has no signal `{}`",
+            type_, signal
+        )));
+    }
+
+    let mut query = std::mem::MaybeUninit::<glib::gobject_ffi::GSignalQuery>::zeroed();
+    let query = unsafe {
+        glib::gobject_ffi::g_signal_query(id, query.as_mut_ptr());
+        query.assume_init()
+    };
+    if query.n_params as usize != param_types.len() {
+        return Err(signal_error(format!(
+            "signal `{}` of `{}` takes {} parameters, the handler expects {}",
+            signal,
+            type_,
+            query.n_params,
+            param_types.len()
+        )));
+    }
+
+    let signal_param_types =
+        unsafe { std::slice::from_raw_parts(query.param_types, query.n_params as usize) };
+    for (i, (&signal_param_type, &param_type)) in
+        signal_param_types.iter().zip(param_types).enumerate()
+    {
+        let signal_param_type: glib::Type =
+            unsafe { from_glib(signal_param_type & !G_SIGNAL_TYPE_STATIC_SCOPE) };
+        if !signal_param_type.is_a(param_type) {
+            return Err(signal_error(format!(
+                "parameter {} of signal `{}` of `{}` is a `{}`, the handler expects a `{}`",
+                i + 1,
+                signal,
+                type_,
+                signal_param_type,
+                param_type
+            )));
+        }
+    }
+
+    let signal_return_type: glib::Type =
+        unsafe { from_glib(query.return_type & !G_SIGNAL_TYPE_STATIC_SCOPE) };
+    if !return_type.is_a(signal_return_type) {
+        return Err(signal_error(format!(
+            "signal `{}` of `{}` returns a `{}`, the handler returns a `{}`",
+            signal, type_, signal_return_type, return_type
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Borrowed string parameters
+//!
+//! `&str`, `&glib::GStr`, `&CStr`, `&Path` and `&OsStr` parameters point
+//! straight into the caller's `const char*` instead of copying it. The
+//! string is checked for `NULL` and, for UTF-8 strings, for valid UTF-8
+//! first; failures are `G_CONVERT_ERROR` errors naming the parameter.
+//!
+//! Paths and OS strings use the GLib filename encoding: the bytes are taken
+//! as they are on Unix, and are UTF-8 on Windows.
+
+use std::{
+    ffi::{CStr, OsStr},
+    os::raw::c_char,
+    path::Path,
+};
+
+use glib::GStr;
+
+/// `G_CONVERT_ERROR_FAILED` for a parameter that must not be `NULL`
+pub fn null_error(param: &str) -> glib::Error {
+    glib::Error::new(
+        glib::ConvertError::Failed,
+        &format!("`{param}` must not be NULL"),
+    )
+}
+
+/// Borrows a `const char*` as a `&CStr`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a nul-terminated string that outlives
+/// `'a`
+pub unsafe fn cstr_from_c<'a>(ptr: *const c_char, param: &str) -> Result<&'a CStr, glib::Error> {
+    if ptr.is_null() {
+        return Err(null_error(param));
+    }
+
+    Ok(unsafe { CStr::from_ptr(ptr) })
+}
+
+/// Borrows a `const char*` as a `&str`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a nul-terminated string that outlives
+/// `'a`
+pub unsafe fn str_from_c<'a>(ptr: *const c_char, param: &str) -> Result<&'a str, glib::Error> {
+    let cstr = unsafe { cstr_from_c(ptr, param)? };
+    cstr.to_str().map_err(|err| {
+        glib::Error::new(
+            glib::ConvertError::IllegalSequence,
+            &format!("`{param}` is not valid UTF-8: {err}"),
+        )
+    })
+}
+
+/// Borrows a `const char*` as a `&GStr`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a nul-terminated string that outlives
+/// `'a`
+pub unsafe fn gstr_from_c<'a>(ptr: *const c_char, param: &str) -> Result<&'a GStr, glib::Error> {
+    let cstr = unsafe { cstr_from_c(ptr, param)? };
+    GStr::from_utf8_with_nul(cstr.to_bytes_with_nul()).map_err(|err| {
+        glib::Error::new(
+            glib::ConvertError::IllegalSequence,
+            &format!("`{param}` is not valid UTF-8: {err}"),
+        )
+    })
+}
+
+/// Borrows a filename-encoded `const char*` as an `&OsStr`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a nul-terminated string that outlives
+/// `'a`
+pub unsafe fn os_str_from_c<'a>(ptr: *const c_char, param: &str) -> Result<&'a OsStr, glib::Error> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let cstr = unsafe { cstr_from_c(ptr, param)? };
+        Ok(OsStr::from_bytes(cstr.to_bytes()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        unsafe { str_from_c(ptr, param) }.map(OsStr::new)
+    }
+}
+
+/// Borrows a filename-encoded `const char*` as a `&Path`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or point to a nul-terminated string that outlives
+/// `'a`
+pub unsafe fn path_from_c<'a>(ptr: *const c_char, param: &str) -> Result<&'a Path, glib::Error> {
+    unsafe { os_str_from_c(ptr, param) }.map(Path::new)
+}
+
+/// Logs a rejected parameter of `function` as a critical, like a failed
+/// `g_return_val_if_fail()`
+pub fn critical(function: &str, error: &glib::Error) {
+    glib::g_log!(glib::LogLevel::Critical, "{}: {}", function, error);
+}
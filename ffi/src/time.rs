@@ -0,0 +1,154 @@
+//! Conversions for durations and points in time
+//!
+//! Durations are `gint64` microseconds like GLib's `GTimeSpan`, or `guint`
+//! milliseconds like GLib timeouts through [`Milliseconds`]. `SystemTime` is
+//! a `gint64` count of microseconds since the Unix epoch, as returned by
+//! `g_get_real_time()`.
+//!
+//! Each type's `c_error_value()` is also the `None` of its `Option`, and is
+//! returned on error whether or not the value is optional. Values that
+//! don't fit the target type saturate at its bounds, stopping short of that
+//! sentinel: a negative duration from C becomes zero, and a duration or
+//! time too large for C becomes the largest other value C can hold.
+
+use std::time::{Duration, SystemTime};
+
+use glib::translate::*;
+
+use crate::FfiConvert;
+
+/// `Duration` as `gint64` microseconds
+///
+/// `-1` is `None` for `Option<Duration>`; other negative values are zero.
+impl FfiConvert for Duration {
+    type CType = i64;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        Duration::from_micros(value.max(0) as u64)
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        i64::try_from(self.as_micros()).unwrap_or(i64::MAX)
+    }
+
+    fn c_error_value() -> Self::CType {
+        -1
+    }
+}
+
+/// `SystemTime` as `gint64` microseconds since the Unix epoch
+///
+/// `G_MININT64` is `None` for `Option<SystemTime>`, so earlier times
+/// saturate at `G_MININT64 + 1`.
+impl FfiConvert for SystemTime {
+    type CType = i64;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        let offset = Duration::from_micros(value.unsigned_abs());
+        if value >= 0 {
+            saturating_offset(offset, |offset| SystemTime::UNIX_EPOCH.checked_add(offset))
+        } else {
+            saturating_offset(offset, |offset| SystemTime::UNIX_EPOCH.checked_sub(offset))
+        }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        let micros = match self.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => after.as_micros() as i128,
+            Err(before) => -(before.duration().as_micros() as i128),
+        };
+        micros.clamp(i64::MIN as i128 + 1, i64::MAX as i128) as i64
+    }
+
+    fn c_error_value() -> Self::CType {
+        i64::MIN
+    }
+}
+
+/// Applies the largest part of `offset` that `apply` can represent
+fn saturating_offset(
+    offset: Duration,
+    apply: impl Fn(Duration) -> Option<SystemTime>,
+) -> SystemTime {
+    if let Some(time) = apply(offset) {
+        return time;
+    }
+
+    let (mut fits, mut overflows) = (Duration::ZERO, offset);
+    while overflows - fits > Duration::from_micros(1) {
+        let mid = fits + (overflows - fits) / 2;
+        if apply(mid).is_some() {
+            fits = mid;
+        } else {
+            overflows = mid;
+        }
+    }
+    apply(fits).expect("a zero offset is always representable")
+}
+
+/// A `Duration` or `Option<Duration>` passed as `guint` milliseconds, used
+/// for values marked `#[ffi(duration = millis)]`
+///
+/// `G_MAXUINT` is `None` for `Option<Duration>`, so longer durations
+/// saturate at `G_MAXUINT - 1`. Durations are truncated to whole
+/// milliseconds.
+pub struct Milliseconds<T>(pub T);
+
+impl FfiConvert for Milliseconds<Duration> {
+    type CType = u32;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        Milliseconds(Duration::from_millis(value.into()))
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        u32::try_from(self.0.as_millis()).map_or(u32::MAX - 1, |millis| millis.min(u32::MAX - 1))
+    }
+
+    fn c_error_value() -> Self::CType {
+        u32::MAX
+    }
+}
+
+impl FfiConvert for Milliseconds<Option<Duration>> {
+    type CType = u32;
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        Milliseconds((value != u32::MAX).then(|| Duration::from_millis(value.into())))
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        match self.0 {
+            Some(duration) => Milliseconds(duration).to_c_owned(),
+            None => u32::MAX,
+        }
+    }
+
+    fn c_error_value() -> Self::CType {
+        u32::MAX
+    }
+}
+
+impl FfiConvert for glib::DateTime {
+    type CType = *mut glib::ffi::GDateTime;
+
+    fn c_is_null(value: Self::CType) -> bool {
+        value.is_null()
+    }
+
+    unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+        unsafe { from_glib_none(value) }
+    }
+
+    unsafe fn from_c_owned(value: Self::CType) -> Self {
+        unsafe { from_glib_full(value) }
+    }
+
+    fn to_c_owned(self) -> Self::CType {
+        IntoGlibPtr::into_glib_ptr(self)
+    }
+
+    fn c_error_value() -> Self::CType {
+        std::ptr::null_mut()
+    }
+}
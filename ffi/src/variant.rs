@@ -0,0 +1,103 @@
+//! Values passed as `GVariant*`
+//!
+//! Values marked `#[ffi(variant)]`, either `glib::Variant` or any type
+//! implementing `ToVariant` and `FromVariant`, cross the boundary as
+//! `GVariant*`. Parameters are checked against their expected type before
+//! Rust sees them; mismatches are `G_IO_ERROR_INVALID_ARGUMENT` errors naming
+//! the parameter. Returned variants are owned by the caller.
+
+use glib::{
+    Variant, VariantTy,
+    translate::*,
+    variant::{FromVariant, StaticVariantType, ToVariant},
+};
+
+fn type_error(param: &str, actual: &VariantTy, expected: &VariantTy) -> glib::Error {
+    glib::Error::new(
+        gio::IOErrorEnum::InvalidArgument,
+        &format!("`{param}` has type `{actual}`, expected `{expected}`"),
+    )
+}
+
+fn check_type(variant: &Variant, expected: &VariantTy, param: &str) -> Result<(), glib::Error> {
+    if variant.is_type(expected) {
+        Ok(())
+    } else {
+        Err(type_error(param, variant.type_(), expected))
+    }
+}
+
+/// Borrows a `GVariant*` of type `type_string`, `None` for `NULL`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or a valid `GVariant`
+pub unsafe fn typed_from_c(
+    ptr: *mut glib::ffi::GVariant,
+    type_string: &str,
+    param: &str,
+) -> Result<Option<Variant>, glib::Error> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+
+    let variant: Variant = unsafe { from_glib_none(ptr) };
+    let expected = VariantTy::new(type_string).expect("Invalid variant type string");
+    check_type(&variant, expected, param)?;
+    Ok(Some(variant))
+}
+
+/// Converts a `GVariant*` of `T`'s type, and of type `type_string` when
+/// given, `None` for `NULL`
+///
+/// # Safety
+///
+/// `ptr` must be `NULL` or a valid `GVariant`
+pub unsafe fn from_c<T: FromVariant + StaticVariantType>(
+    ptr: *mut glib::ffi::GVariant,
+    type_string: Option<&str>,
+    param: &str,
+) -> Result<Option<T>, glib::Error> {
+    let variant = match type_string {
+        Some(type_string) => unsafe { typed_from_c(ptr, type_string, param)? },
+        None if ptr.is_null() => None,
+        None => Some(unsafe { from_glib_none(ptr) }),
+    };
+    let Some(variant) = variant else {
+        return Ok(None);
+    };
+
+    let expected = T::static_variant_type();
+    check_type(&variant, &expected, param)?;
+    T::from_variant(&variant).map(Some).ok_or_else(|| {
+        glib::Error::new(
+            gio::IOErrorEnum::InvalidArgument,
+            &format!("`{param}` holds an invalid `{expected}` value"),
+        )
+    })
+}
+
+/// Rejects a `NULL` variant for a parameter that isn't an `Option`
+pub fn required<T>(value: Option<T>, param: &str) -> Result<T, glib::Error> {
+    value.ok_or_else(|| crate::strings::null_error(param))
+}
+
+/// Hands a `glib::Variant` of type `type_string` to the caller
+pub fn typed_to_c(variant: Variant, type_string: &str) -> *mut glib::ffi::GVariant {
+    debug_assert!(
+        variant.is_type(VariantTy::new(type_string).expect("Invalid variant type string")),
+        "returned variant has type `{}`, expected `{type_string}`",
+        variant.type_(),
+    );
+    variant.into_glib_ptr()
+}
+
+/// Converts `value` into a `GVariant*` owned by the caller, of type
+/// `type_string` when given
+pub fn to_c<T: ToVariant>(value: &T, type_string: Option<&str>) -> *mut glib::ffi::GVariant {
+    let variant = value.to_variant();
+    match type_string {
+        Some(type_string) => typed_to_c(variant, type_string),
+        None => variant.into_glib_ptr(),
+    }
+}
@@ -27,6 +27,9 @@ fn generate_type_alias(
                 types::FfiType::Object => quote! {
                     pub type #c_type_name = <imp::#type_name as ::glib::subclass::prelude::ObjectSubclass>::Instance;
                 },
+                types::FfiType::Interface => quote! {
+                    pub type #c_type_name = <imp::#type_name as ::glib::subclass::prelude::ObjectInterface>::Instance;
+                },
                 types::FfiType::Shared => quote! {
                     pub type #c_type_name = *mut <<super::#type_name as ::glib::subclass::shared::SharedType>::RefCountedType as ::glib::subclass::shared::RefCounted>::InnerType;
                 },
@@ -39,6 +42,9 @@ fn generate_type_alias(
                 types::FfiType::Flags => quote! {
                     pub type #c_type_name = super::#type_name;
                 },
+                types::FfiType::Struct => quote! {
+                    pub type #c_type_name = super::#type_name;
+                },
             };
         }
     }
@@ -58,6 +64,867 @@ fn generate_get_type_fn(prefix: &str, self_type: &Type) -> proc_macro2::TokenStr
     }
 }
 
+/// `repr(C)` class or interface struct holding the virtual method slots,
+/// which the type must use as its `ObjectSubclass::Class` or
+/// `ObjectInterface::Interface`
+fn generate_vtable_struct(
+    ffi_type: types::FfiType,
+    self_type: &Type,
+    c_type_name: &syn::Ident,
+    slots: &[proc_macro2::TokenStream],
+    inits: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let Type::Path(type_path) = self_type else {
+        return quote! {};
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return quote! {};
+    };
+    let type_name = &last_segment.ident;
+
+    if ffi_type == types::FfiType::Interface {
+        let interface_name = quote::format_ident!("{}Interface", c_type_name);
+        return quote! {
+            #[derive(Clone, Copy)]
+            #[repr(C)]
+            pub struct #interface_name {
+                pub parent_iface: ::glib::gobject_ffi::GTypeInterface,
+                #(#slots,)*
+            }
+
+            unsafe impl ::glib::subclass::types::InterfaceStruct for #interface_name {
+                type Type = imp::#type_name;
+
+                fn interface_init(&mut self) {
+                    #(#inits)*
+                }
+            }
+
+            const _: () = {
+                fn interface_struct_is<T: ::glib::subclass::prelude::ObjectInterface<Interface = #interface_name>>() {}
+                let _ = interface_struct_is::<imp::#type_name>;
+            };
+        };
+    }
+
+    let class_name = quote::format_ident!("{}Class", c_type_name);
+    quote! {
+        #[repr(C)]
+        pub struct #class_name {
+            pub parent_class: <<imp::#type_name as ::glib::subclass::prelude::ObjectSubclass>::ParentType as ::glib::object::ObjectType>::GlibClassType,
+            #(#slots,)*
+        }
+
+        unsafe impl ::glib::subclass::types::ClassStruct for #class_name {
+            type Type = imp::#type_name;
+
+            fn class_init(&mut self) {
+                unsafe {
+                    let base = &mut *(self as *mut _
+                        as *mut ::glib::Class<<imp::#type_name as ::glib::subclass::prelude::ObjectSubclass>::ParentType>);
+                    <<imp::#type_name as ::glib::subclass::prelude::ObjectSubclass>::ParentType as ::glib::subclass::types::IsSubclassable<imp::#type_name>>::class_init(base);
+                }
+                #(#inits)*
+            }
+        }
+
+        const _: () = {
+            fn class_struct_is<T: ::glib::subclass::prelude::ObjectSubclass<Class = #class_name>>() {}
+            let _ = class_struct_is::<imp::#type_name>;
+        };
+    }
+}
+
+/// Checks the declared parent and interfaces against the subclass, so the
+/// header can't drift from the registered type. Undeclared ones are read
+/// from the `GType` by [`generate_manifest_impl`] instead, except for the
+/// parent of a class struct laid out in the header, which is `GObject`
+/// unless declared otherwise
+fn generate_type_assertions(
+    ffi_type: types::FfiType,
+    self_type: &Type,
+    parent: Option<&types::TypeRef>,
+    implements: &[types::TypeRef],
+    lays_out_parent: bool,
+) -> proc_macro2::TokenStream {
+    let Type::Path(type_path) = self_type else {
+        return quote! {};
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return quote! {};
+    };
+    let type_name = &last_segment.ident;
+
+    let interfaces = implements.iter().map(|interface| &interface.path);
+    if ffi_type != types::FfiType::Object {
+        // An interface lists its prerequisites with `@requires` in
+        // `glib::wrapper!`, which makes it `IsA` them as well
+        return quote! {
+            const _: () = {
+                fn implements<T: ::glib::prelude::IsA<I>, I: ::glib::prelude::ObjectType>() {}
+                #(let _ = implements::<#self_type, #interfaces>;)*
+            };
+        };
+    }
+
+    let parent = match parent {
+        Some(parent) => {
+            let path = &parent.path;
+            Some(quote! { #path })
+        }
+        None if lays_out_parent => Some(quote! { ::glib::Object }),
+        None => None,
+    };
+    let parent_assertion = parent.map(|parent| {
+        quote! {
+            fn parent_is<T: ::glib::subclass::prelude::ObjectSubclass<ParentType = #parent>>() {}
+            let _ = parent_is::<imp::#type_name>;
+        }
+    });
+    let interfaces_assertion = (!implements.is_empty()).then(|| {
+        quote! {
+            fn interfaces_are<T: ::glib::subclass::prelude::ObjectSubclass<Interfaces = (#(#interfaces,)*)>>() {}
+            let _ = interfaces_are::<imp::#type_name>;
+        }
+    });
+    quote! {
+        const _: () = {
+            #parent_assertion
+            #interfaces_assertion
+        };
+    }
+}
+
+/// `FfiManifest` impl naming the C type and its `get_type` function, so the
+/// hierarchy can be read back from the registered type
+fn generate_manifest_impl(
+    ffi_type: types::FfiType,
+    self_type: &Type,
+    c_type_name: &str,
+    prefix: &str,
+) -> proc_macro2::TokenStream {
+    if !ffi_type.is_gobject() {
+        return quote! {};
+    }
+    let get_type = format!("{}_get_type", prefix);
+    quote! {
+        impl ::gobject_ffi::manifest::FfiManifest for #self_type {
+            const C_TYPE_NAME: &'static str = #c_type_name;
+            const GET_TYPE: &'static str = #get_type;
+        }
+    }
+}
+
+/// `G_STATIC_ASSERT`s checking that the header's struct lays out the
+/// declared fields in order, each at the next offset aligned for its C
+/// type. They're written in terms of `sizeof` and `G_ALIGNOF` so they hold
+/// on every ABI, while the Rust side checks its layout against the same C
+/// types
+fn record_header_asserts(c_type_name: &str, fields: &[types::FfiField]) -> String {
+    let aligned = |end: String, align: String| format!("({end} + {align} - 1) / {align} * {align}");
+    let mut asserts = String::new();
+    let mut end = "0".to_string();
+    for field in fields {
+        let c_type = utils::rust_type_to_c_type_string(&field.ty);
+        let offset = format!("G_STRUCT_OFFSET ({c_type_name}, {})", field.ident);
+        let expected = if end == "0" {
+            end.clone()
+        } else {
+            aligned(end, format!("G_ALIGNOF ({c_type})"))
+        };
+        asserts.push_str(&format!("G_STATIC_ASSERT ({offset} == {expected});\n"));
+        end = format!("{offset} + sizeof ({c_type})");
+    }
+    if !fields.is_empty() {
+        asserts.push_str(&format!(
+            "G_STATIC_ASSERT (sizeof ({c_type_name}) == {});\n",
+            aligned(end, format!("G_ALIGNOF ({c_type_name})"))
+        ));
+    }
+    asserts.push('\n');
+    asserts
+}
+
+/// Boxed `GType` and `FfiConvert` support for a `ty = "struct"` record,
+/// plus assertions that its Rust layout matches the declared C fields.
+/// The fields are mirrored in a `repr(C)` struct of their C types, so a
+/// `bool` (a 4-byte `gboolean` in C) or a missing field fails to compile
+/// even when padding would hide the difference
+fn generate_record_impls(
+    self_type: &Type,
+    c_type_name: &str,
+    fields: &[types::FfiField],
+) -> proc_macro2::TokenStream {
+    let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let mismatch = format!(
+        "the layout of `{}` does not match its C declaration",
+        quote! { #self_type }
+    );
+    quote! {
+        const _: () = {
+            #[repr(C)]
+            struct CLayout {
+                #(#idents: <#types as ::gobject_ffi::FfiConvert>::CType,)*
+            }
+
+            assert!(
+                ::std::mem::size_of::<#self_type>() == ::std::mem::size_of::<CLayout>(),
+                #mismatch
+            );
+            assert!(
+                ::std::mem::align_of::<#self_type>() == ::std::mem::align_of::<CLayout>(),
+                #mismatch
+            );
+            #(
+                assert!(
+                    ::std::mem::offset_of!(#self_type, #idents)
+                        == ::std::mem::offset_of!(CLayout, #idents),
+                    #mismatch
+                );
+                assert!(
+                    ::std::mem::size_of::<#types>()
+                        == ::std::mem::size_of::<<#types as ::gobject_ffi::FfiConvert>::CType>(),
+                    #mismatch
+                );
+            )*
+
+            // Every Rust field is declared, even one fitting in padding
+            fn field_types(value: &#self_type) -> #self_type {
+                #(let _: &#types = &value.#idents;)*
+                #self_type { #(#idents: value.#idents),* }
+            }
+            let _ = field_types;
+
+            // Copies are plain `memcpy()`s
+            fn is_copy<T: ::std::marker::Copy>() {}
+            let _ = is_copy::<#self_type>;
+        };
+
+        impl ::glib::prelude::StaticType for #self_type {
+            fn static_type() -> ::glib::Type {
+                static TYPE: ::std::sync::OnceLock<::glib::Type> = ::std::sync::OnceLock::new();
+
+                unsafe extern "C" fn copy(value: ::glib::ffi::gpointer) -> ::glib::ffi::gpointer {
+                    let size = ::std::mem::size_of::<#self_type>();
+                    unsafe {
+                        let copy = ::glib::ffi::g_malloc(size);
+                        ::std::ptr::copy_nonoverlapping(value as *const u8, copy as *mut u8, size);
+                        copy
+                    }
+                }
+
+                *TYPE.get_or_init(|| unsafe {
+                    ::glib::translate::from_glib(::glib::gobject_ffi::g_boxed_type_register_static(
+                        concat!(#c_type_name, "\0").as_ptr() as *const ::std::os::raw::c_char,
+                        Some(copy),
+                        Some(::glib::ffi::g_free),
+                    ))
+                })
+            }
+        }
+
+        // Borrowed as a pointer to the caller's storage, returned as a
+        // heap copy freed with `g_boxed_free()`
+        impl ::gobject_ffi::FfiConvert for #self_type {
+            type CType = *mut #self_type;
+
+            unsafe fn from_c_borrowed(value: Self::CType) -> Self {
+                unsafe { *value }
+            }
+
+            fn to_c_owned(self) -> Self::CType {
+                unsafe {
+                    let copy = ::glib::ffi::g_malloc(::std::mem::size_of::<Self>()) as Self::CType;
+                    copy.write(self);
+                    copy
+                }
+            }
+
+            fn c_error_value() -> Self::CType {
+                ::std::ptr::null_mut()
+            }
+
+            fn c_is_null(value: Self::CType) -> bool {
+                value.is_null()
+            }
+        }
+
+        // SAFETY: the layout was checked against the C declaration above
+        unsafe impl ::gobject_ffi::FfiRecord for #self_type {}
+    }
+}
+
+/// `_get_type` declaration followed by the `G_DECLARE_*_TYPE`-style macros
+/// and `g_autoptr()` support, such as `MY_TYPE_CALCULATOR` and
+/// `MY_IS_CALCULATOR()` for `MyCalculator`
+fn generate_type_macros(
+    ffi_type: types::FfiType,
+    namespace: &str,
+    type_snake: &str,
+    prefix: &str,
+    c_type_name: &str,
+    has_vtable: bool,
+) -> String {
+    use heck::ToShoutySnakeCase;
+
+    let (namespace_upper, type_upper) = (
+        namespace.to_shouty_snake_case(),
+        type_snake.to_shouty_snake_case(),
+    );
+    let join = |parts: &[&str]| {
+        parts
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("_")
+    };
+    let type_macro = join(&[&namespace_upper, "TYPE", &type_upper]);
+    let cast_macro = join(&[&namespace_upper, &type_upper]);
+    let is_macro = join(&[&namespace_upper, "IS", &type_upper]);
+
+    let mut header = format!("GType {}_get_type(void);\n", prefix);
+    header.push_str(&format!(
+        "#define {} ({}_get_type ())\n",
+        type_macro, prefix
+    ));
+
+    match ffi_type {
+        types::FfiType::Object | types::FfiType::Interface => {
+            header.push_str(&format!(
+                "#define {}(obj) (G_TYPE_CHECK_INSTANCE_CAST ((obj), {}, {}))\n",
+                cast_macro, type_macro, c_type_name
+            ));
+            header.push_str(&format!(
+                "#define {}(obj) (G_TYPE_CHECK_INSTANCE_TYPE ((obj), {}))\n",
+                is_macro, type_macro
+            ));
+            if has_vtable && ffi_type == types::FfiType::Interface {
+                header.push_str(&format!(
+                    "#define {}_GET_IFACE(obj) (G_TYPE_INSTANCE_GET_INTERFACE ((obj), {}, {}Interface))\n",
+                    cast_macro, type_macro, c_type_name
+                ));
+            } else if has_vtable {
+                header.push_str(&format!(
+                    "#define {}_CLASS(klass) (G_TYPE_CHECK_CLASS_CAST ((klass), {}, {}Class))\n",
+                    cast_macro, type_macro, c_type_name
+                ));
+                header.push_str(&format!(
+                    "#define {}_CLASS(klass) (G_TYPE_CHECK_CLASS_TYPE ((klass), {}))\n",
+                    is_macro, type_macro
+                ));
+                header.push_str(&format!(
+                    "#define {}_GET_CLASS(obj) (G_TYPE_INSTANCE_GET_CLASS ((obj), {}, {}Class))\n",
+                    cast_macro, type_macro, c_type_name
+                ));
+            }
+            header.push_str(&format!(
+                "\nG_DEFINE_AUTOPTR_CLEANUP_FUNC ({}, g_object_unref)\n",
+                c_type_name
+            ));
+        }
+        types::FfiType::Boxed | types::FfiType::Shared | types::FfiType::Struct => {
+            let (copy, free) = lifecycle_names(ffi_type).expect("boxed, shared and struct types");
+            let (copy_doc, free_doc, const_) = match ffi_type {
+                types::FfiType::Boxed => (
+                    "Copies @self.\n *\n * Returns: (transfer full): a copy of @self",
+                    "Frees @self.".to_string(),
+                    "",
+                ),
+                types::FfiType::Struct => (
+                    "Copies @self to the heap.\n *\n * Returns: (transfer full): a copy of @self",
+                    format!("Frees a copy made by {}_{}().", prefix, copy),
+                    "const ",
+                ),
+                _ => (
+                    "Takes a reference to @self.\n *\n * Returns: (transfer full): @self",
+                    "Releases a reference to @self, freeing it once the last one is gone."
+                        .to_string(),
+                    "",
+                ),
+            };
+            header.push_str(&format!(
+                "\n/**\n * {prefix}_{copy}:\n * @self: a #{c_type_name}\n *\n * {copy_doc}\n */\n{c_type_name}* {prefix}_{copy}({const_}{c_type_name}* self);\n"
+            ));
+            header.push_str(&format!(
+                "\n/**\n * {prefix}_{free}:\n * @self: (transfer full): a #{c_type_name}\n *\n * {free_doc}\n */\nvoid {prefix}_{free}({c_type_name}* self);\n"
+            ));
+            header.push_str(&format!(
+                "\nG_DEFINE_AUTOPTR_CLEANUP_FUNC ({c_type_name}, {prefix}_{free})\n"
+            ));
+        }
+        types::FfiType::Enum | types::FfiType::Flags => {}
+    }
+
+    header.push('\n');
+    header
+}
+
+/// gtk-doc block for the type, naming its parent and interfaces or
+/// describing the fields of a record
+fn generate_type_header(
+    ffi_type: types::FfiType,
+    c_type_name: &str,
+    prefix: &str,
+    parent: Option<&types::TypeRef>,
+    implements: &[types::TypeRef],
+    fields: &[types::FfiField],
+) -> Option<String> {
+    if let Some((copy, free)) = lifecycle_names(ffi_type) {
+        let field_docs = fields
+            .iter()
+            .filter_map(|field| {
+                let doc = field.doc.as_ref()?;
+                Some(format!(" * @{}: {}\n", field.ident, doc))
+            })
+            .collect::<String>();
+        return Some(format!(
+            "/**\n * {}: (copy-func {}_{}) (free-func {}_{})\n{} */\n",
+            c_type_name, prefix, copy, prefix, free, field_docs
+        ));
+    }
+    if !ffi_type.is_gobject() || (parent.is_none() && implements.is_empty()) {
+        return None;
+    }
+
+    let interfaces = implements
+        .iter()
+        .map(|interface| format!("#{}", interface.c_type_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let description = if ffi_type == types::FfiType::Interface {
+        format!("Requires {}.", interfaces)
+    } else {
+        match parent {
+            Some(parent) if interfaces.is_empty() => {
+                format!("Derives from #{}.", parent.c_type_name)
+            }
+            Some(parent) => format!(
+                "Derives from #{} and implements {}.",
+                parent.c_type_name, interfaces
+            ),
+            None => format!("Implements {}.", interfaces),
+        }
+    };
+
+    Some(format!(
+        "/**\n * {}:\n *\n * {}\n */\n",
+        c_type_name, description
+    ))
+}
+
+/// Suffixes of the functions taking and releasing a boxed or shared value
+fn lifecycle_names(ffi_type: types::FfiType) -> Option<(&'static str, &'static str)> {
+    match ffi_type {
+        types::FfiType::Boxed => Some(("copy", "free")),
+        types::FfiType::Shared => Some(("ref", "unref")),
+        types::FfiType::Struct => Some(("copy", "free")),
+        _ => None,
+    }
+}
+
+/// `_copy`/`_free` or `_ref`/`_unref` exports of a boxed or shared type.
+/// Both go through `g_boxed_copy()`/`g_boxed_free()`, which take and drop a
+/// reference for shared types
+fn generate_lifecycle_fns(
+    ffi_type: types::FfiType,
+    prefix: &str,
+    self_type: &Type,
+    c_type_name: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let Some((copy, free)) = lifecycle_names(ffi_type) else {
+        return quote! {};
+    };
+    let copy_fn = quote::format_ident!("{}_{}", prefix, copy);
+    let free_fn = quote::format_ident!("{}_{}", prefix, free);
+    // Records are copied from a pointer to their storage, wherever it lives
+    let (borrowed, owned) = if ffi_type == types::FfiType::Struct {
+        (quote! { *const #c_type_name }, quote! { *mut #c_type_name })
+    } else {
+        (quote! { #c_type_name }, quote! { #c_type_name })
+    };
+
+    quote! {
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #copy_fn(self_param: #borrowed) -> #owned {
+            let type_ = ::glib::translate::IntoGlib::into_glib(
+                <super::#self_type as ::glib::prelude::StaticType>::static_type(),
+            );
+            unsafe {
+                ::glib::gobject_ffi::g_boxed_copy(type_, self_param as ::glib::ffi::gconstpointer)
+                    as #owned
+            }
+        }
+
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #free_fn(self_param: #owned) {
+            let type_ = ::glib::translate::IntoGlib::into_glib(
+                <super::#self_type as ::glib::prelude::StaticType>::static_type(),
+            );
+            unsafe { ::glib::gobject_ffi::g_boxed_free(type_, self_param as ::glib::ffi::gpointer) }
+        }
+    }
+}
+
+/// A field of a tagged union variant: the binding used to match it, its
+/// name in C and its Rust type
+struct VariantField<'a> {
+    binding: syn::Ident,
+    c_name: String,
+    ty: &'a Type,
+}
+
+fn variant_fields(variant: &syn::Variant) -> Vec<VariantField<'_>> {
+    let count = variant.fields.len();
+    variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match field.ident {
+            Some(ref ident) => VariantField {
+                binding: ident.clone(),
+                c_name: ident.to_string(),
+                ty: &field.ty,
+            },
+            None => {
+                let c_name = if count == 1 {
+                    "value".to_string()
+                } else {
+                    format!("value_{}", i)
+                };
+                VariantField {
+                    binding: syn::Ident::new(&c_name, proc_macro2::Span::call_site()),
+                    c_name,
+                    ty: &field.ty,
+                }
+            }
+        })
+        .collect()
+}
+
+/// `_get_kind()` with its generated `GEnum` of variants, plus a `_new_`
+/// constructor and a `_get_` accessor per variant of a boxed Rust enum.
+/// An exhaustive `match` binding every declared field with its declared
+/// type keeps the declared variants in sync with the enum.
+/// Returns the exports, the header declaring the kind enum and the headers
+/// of the functions
+fn generate_tagged_union(
+    namespace: &str,
+    type_snake: &str,
+    prefix: &str,
+    self_type: &Type,
+    c_type_name: &syn::Ident,
+    variants: &[syn::Variant],
+) -> (proc_macro2::TokenStream, String, Vec<String>) {
+    use heck::ToShoutySnakeCase;
+
+    let kind_ident = quote::format_ident!("{}Kind", c_type_name);
+    let kind_name = kind_ident.to_string();
+    let kind_get_type = quote::format_ident!("{}_kind_get_type", prefix);
+    let get_kind = quote::format_ident!("{}_get_kind", prefix);
+    let value_names = variants
+        .iter()
+        .map(|variant| {
+            format!(
+                "{}_KIND_{}",
+                prefix.to_shouty_snake_case(),
+                variant.ident.to_string().to_shouty_snake_case()
+            )
+        })
+        .collect::<Vec<_>>();
+    let nicks = variants
+        .iter()
+        .map(|variant| variant.ident.to_string().to_snake_case().replace('_', "-"));
+    let variant_idents = variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let declared = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let fields = variant_fields(variant);
+        let bindings = fields
+            .iter()
+            .map(|field| &field.binding)
+            .collect::<Vec<_>>();
+        let types = fields.iter().map(|field| field.ty);
+        let pattern = match variant.fields {
+            syn::Fields::Named(_) => quote! { super::#self_type::#ident { #(#bindings,)* } },
+            syn::Fields::Unnamed(_) => quote! { super::#self_type::#ident(#(#bindings,)*) },
+            syn::Fields::Unit => quote! { super::#self_type::#ident },
+        };
+        quote::quote_spanned! {variant.ident.span()=>
+            #pattern => {
+                #(let _: &#types = #bindings;)*
+            }
+        }
+    });
+    let get_kind_name = get_kind.to_string();
+    // Returned for a `NULL` self, like `g_return_val_if_fail()` does
+    let first_variant = variant_idents[0];
+
+    let mut exports = quote! {
+        // `variants(...)` must match the enum
+        const _: () = {
+            fn declared_variants(value: &super::#self_type) {
+                match value {
+                    #(#declared)*
+                }
+            }
+            let _ = declared_variants;
+        };
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::glib::Enum)]
+        #[enum_type(name = #kind_name)]
+        #[repr(i32)]
+        pub enum #kind_ident {
+            #(
+                #[enum_value(name = #value_names, nick = #nicks)]
+                #variant_idents,
+            )*
+        }
+
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #kind_get_type() -> ::glib::ffi::GType {
+            ::glib::translate::IntoGlib::into_glib(<#kind_ident as ::glib::prelude::StaticType>::static_type())
+        }
+
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #get_kind(self_param: #c_type_name) -> #kind_ident {
+            if self_param.is_null() {
+                ::gobject_ffi::strings::critical(#get_kind_name, &::gobject_ffi::strings::null_error("self"));
+                return #kind_ident::#first_variant;
+            }
+            let self_param: ::glib::translate::Borrowed<super::#self_type> =
+                unsafe { ::glib::translate::from_glib_borrow(self_param) };
+            match &*self_param {
+                #(super::#self_type::#variant_idents { .. } => #kind_ident::#variant_idents,)*
+            }
+        }
+    };
+
+    let value_docs = variants
+        .iter()
+        .zip(&value_names)
+        .filter_map(|(variant, value_name)| {
+            let doc = utils::doc_string(&variant.attrs)?;
+            Some(format!(" * @{}: {}\n", value_name, doc))
+        })
+        .collect::<String>();
+    let kind_header = format!(
+        "/**\n * {kind_name}:\n{value_docs} *\n * The variants of a #{c_type_name}, as returned by {prefix}_get_kind().\n */\ntypedef enum {{\n{values}}} {kind_name};\n\nGType {prefix}_kind_get_type(void);\n#define {type_macro} ({prefix}_kind_get_type ())\n\n",
+        values = value_names
+            .iter()
+            .map(|value_name| format!("    {},\n", value_name))
+            .collect::<String>(),
+        type_macro = [namespace, "type", type_snake, "kind"]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .map(|part| part.to_shouty_snake_case())
+            .collect::<Vec<_>>()
+            .join("_"),
+    );
+
+    let mut headers = vec![format!(
+        "/**\n * {prefix}_get_kind:\n * @self: a #{c_type_name}\n *\n * Returns: the variant held by @self, or %{first_value} if @self is %NULL\n */\n{kind_name} {prefix}_get_kind({c_type_name}* self);\n",
+        first_value = value_names[0],
+    )];
+
+    for (variant, value_name) in variants.iter().zip(&value_names) {
+        let ident = &variant.ident;
+        let snake = ident.to_string().to_snake_case();
+        let fields = variant_fields(variant);
+        let bindings = fields
+            .iter()
+            .map(|field| &field.binding)
+            .collect::<Vec<_>>();
+        let types = fields.iter().map(|field| field.ty).collect::<Vec<_>>();
+
+        let new_fn = quote::format_ident!("{}_new_{}", prefix, snake);
+        let new_fn_name = new_fn.to_string();
+        let c_names = fields.iter().map(|field| &field.c_name);
+        let value = match variant.fields {
+            syn::Fields::Named(_) => {
+                quote! { super::#self_type::#ident { #(#bindings,)* } }
+            }
+            syn::Fields::Unnamed(_) => quote! { super::#self_type::#ident(#(#bindings,)*) },
+            syn::Fields::Unit => quote! { super::#self_type::#ident },
+        };
+        let to_c = types::TransferMode::Full.convert_to(quote! { value });
+        exports.extend(quote! {
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn #new_fn(
+                #(#bindings: <#types as ::gobject_ffi::FfiConvert>::CType,)*
+            ) -> #c_type_name {
+                #(
+                    let #bindings: #types = match unsafe {
+                        <#types as ::gobject_ffi::FfiConvert>::try_from_c_borrowed(#bindings, #c_names)
+                    } {
+                        Ok(value) => value,
+                        Err(err) => {
+                            ::gobject_ffi::strings::critical(#new_fn_name, &err);
+                            return ::std::ptr::null_mut();
+                        }
+                    };
+                )*
+                let value = #value;
+                #to_c
+            }
+        });
+
+        let params = fields
+            .iter()
+            .map(|field| {
+                // Strings are only read
+                let value_type = utils::extract_option_inner(field.ty).unwrap_or(field.ty);
+                let constness = if utils::is_string_type(value_type) {
+                    "const "
+                } else {
+                    ""
+                };
+                format!(
+                    "{}{} {}",
+                    constness,
+                    utils::rust_type_to_c_type_string(field.ty),
+                    field.c_name
+                )
+            })
+            .collect::<Vec<_>>();
+        let param_docs = fields
+            .iter()
+            .map(|field| {
+                let transfer = if utils::is_primitive_type(field.ty) {
+                    ""
+                } else {
+                    "(transfer none): "
+                };
+                format!(
+                    " * @{}: {}the {} field\n",
+                    field.c_name, transfer, field.c_name
+                )
+            })
+            .collect::<String>();
+        headers.push(format!(
+            "/**\n * {prefix}_new_{snake}:\n{param_docs} *\n * Creates a #{c_type_name} holding a %{value_name}.\n *\n * Returns: (transfer full): a new #{c_type_name}\n */\n{c_type_name}* {prefix}_new_{snake}({});\n",
+            if params.is_empty() {
+                "void".to_string()
+            } else {
+                params.join(", ")
+            }
+        ));
+
+        // Unit variants carry nothing beyond their kind
+        if fields.is_empty() {
+            continue;
+        }
+
+        let get_fn = quote::format_ident!("{}_get_{}", prefix, snake);
+        let get_fn_name = get_fn.to_string();
+        let outs = fields
+            .iter()
+            .map(|field| quote::format_ident!("out_{}", field.c_name))
+            .collect::<Vec<_>>();
+        let pattern = match variant.fields {
+            syn::Fields::Named(_) => quote! { super::#self_type::#ident { #(#bindings,)* } },
+            _ => quote! { super::#self_type::#ident(#(#bindings,)*) },
+        };
+        exports.extend(quote! {
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn #get_fn(
+                self_param: #c_type_name,
+                #(#outs: *mut <#types as ::gobject_ffi::FfiConvert>::CType,)*
+            ) -> ::glib::ffi::gboolean {
+                if self_param.is_null() {
+                    ::gobject_ffi::strings::critical(#get_fn_name, &::gobject_ffi::strings::null_error("self"));
+                    return ::glib::ffi::GFALSE;
+                }
+                let self_param: ::glib::translate::Borrowed<super::#self_type> =
+                    unsafe { ::glib::translate::from_glib_borrow(self_param) };
+                #[allow(unreachable_patterns)]
+                match &*self_param {
+                    #pattern => {
+                        #(
+                            if !#outs.is_null() {
+                                unsafe {
+                                    *#outs = <#types as ::gobject_ffi::FfiConvert>::to_c_owned(
+                                        ::std::clone::Clone::clone(#bindings),
+                                    );
+                                }
+                            }
+                        )*
+                        ::glib::ffi::GTRUE
+                    }
+                    _ => ::glib::ffi::GFALSE,
+                }
+            }
+        });
+
+        let out_params = fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}* out_{}",
+                    utils::rust_type_to_c_type_string(field.ty),
+                    field.c_name
+                )
+            })
+            .collect::<Vec<_>>();
+        let out_docs = fields
+            .iter()
+            .map(|field| {
+                let transfer = if utils::is_primitive_type(field.ty) {
+                    ""
+                } else {
+                    " (transfer full)"
+                };
+                format!(
+                    " * @out_{}: (out) (optional){}: return location for the {} field\n",
+                    field.c_name, transfer, field.c_name
+                )
+            })
+            .collect::<String>();
+        headers.push(format!(
+            "/**\n * {prefix}_get_{snake}:\n * @self: a #{c_type_name}\n{out_docs} *\n * Reads the fields of @self if it holds a %{value_name}.\n *\n * Returns: %TRUE if @self holds a %{value_name}, %FALSE otherwise\n */\ngboolean {prefix}_get_{snake}({c_type_name}* self, {});\n",
+            out_params.join(", ")
+        ));
+    }
+
+    (exports, kind_header, headers)
+}
+
+/// gtk-doc block for a declared property, linking it to its accessors
+fn generate_property_header(c_type_name: &str, property: &types::FfiProperty) -> String {
+    let mut annotations = String::new();
+    if property.readable {
+        annotations.push_str(&format!(" (getter get_{})", property.ident));
+    }
+    if property.writable && !property.construct_only {
+        annotations.push_str(&format!(" (setter set_{})", property.ident));
+    }
+
+    let mut flags = Vec::new();
+    if property.readable {
+        flags.push("readable");
+    }
+    if property.writable {
+        flags.push("writable");
+    }
+    if property.construct_only {
+        flags.push("construct-only");
+    } else if property.construct {
+        flags.push("construct");
+    }
+
+    format!(
+        "/**\n * {}:{}:{}\n *\n * Flags: {}\n */\n",
+        c_type_name,
+        property.name,
+        annotations,
+        flags.join(", ")
+    )
+}
+
 /// Generate FFI wrappers for all methods in an impl block
 #[proc_macro_attribute]
 pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -99,11 +966,92 @@ pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let ffi_type = args.ty;
 
+    if let Some(ref parent) = args.parent {
+        if ffi_type != types::FfiType::Object {
+            return syn::Error::new_spanned(&parent.path, "`parent` requires an object type")
+                .to_compile_error()
+                .into();
+        }
+    }
+    if let Some(interface) = args.implements.first() {
+        if !ffi_type.is_gobject() {
+            return syn::Error::new_spanned(
+                &interface.path,
+                "`implements` requires an object or interface type",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+    match args.fields.first() {
+        Some(field) if ffi_type != types::FfiType::Struct => {
+            return syn::Error::new_spanned(&field.ident, "`fields` requires a struct type")
+                .to_compile_error()
+                .into();
+        }
+        None if ffi_type == types::FfiType::Struct => {
+            return syn::Error::new_spanned(
+                self_type,
+                "struct types must declare their `fields(...)` in C order",
+            )
+            .to_compile_error()
+            .into();
+        }
+        _ => {}
+    }
+    if let Some(variant) = args.variants.first() {
+        if ffi_type != types::FfiType::Boxed {
+            return syn::Error::new_spanned(&variant.ident, "`variants` requires a boxed type")
+                .to_compile_error()
+                .into();
+        }
+    }
+    let parent_c_type_name = args
+        .parent
+        .as_ref()
+        .map_or("GObject", |parent| parent.c_type_name.as_str());
+
     let mut ffi_functions = Vec::new();
     let mut headers = Vec::new();
+    let mut vfunc_slots = Vec::new();
+    let mut vfunc_inits = Vec::new();
+    let mut vfunc_headers = Vec::new();
+
+    let (union_fns, kind_header, union_headers) = if args.variants.is_empty() {
+        (quote! {}, String::new(), Vec::new())
+    } else {
+        generate_tagged_union(
+            &args.prefix.value(),
+            &type_name_lower,
+            &prefix,
+            self_type,
+            &c_type_name,
+            &args.variants,
+        )
+    };
+    ffi_functions.push(union_fns);
+    if args.generate_header.is_some() {
+        headers.extend(union_headers);
+    }
 
     for item in &input.items {
         if let ImplItem::Fn(method) = item {
+            if let Some((copy, free)) = lifecycle_names(ffi_type) {
+                if method.sig.ident == copy || method.sig.ident == free {
+                    ffi_functions.push(
+                        syn::Error::new_spanned(
+                            &method.sig.ident,
+                            format!(
+                                "`{}_{}` is already generated for this type",
+                                prefix, method.sig.ident
+                            ),
+                        )
+                        .to_compile_error(),
+                    );
+                    continue;
+                }
+            }
+
             let has_self = method
                 .sig
                 .inputs
@@ -131,6 +1079,10 @@ pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                 headers.push(ffi_method.generate_header());
             }
 
+            vfunc_slots.extend(ffi_method.generate_vfunc_slot());
+            vfunc_inits.extend(ffi_method.generate_vfunc_init());
+            vfunc_headers.extend(ffi_method.generate_vfunc_header());
+
             let generated = if ffi_method.is_async {
                 ffi_method.generate_async()
             } else {
@@ -141,31 +1093,153 @@ pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
+    for property in &args.properties {
+        let accessors = match method::FfiMethod::from_property(
+            property,
+            &prefix,
+            self_type,
+            &c_type_name,
+            ffi_type,
+        ) {
+            Ok(accessors) => accessors,
+            Err(e) => {
+                ffi_functions.push(e.to_compile_error());
+                continue;
+            }
+        };
+
+        for accessor in accessors {
+            if args.generate_header.is_some() {
+                headers.push(accessor.generate_header());
+            }
+            ffi_functions.push(accessor.generate_sync());
+        }
+
+        if args.generate_header.is_some() {
+            headers.push(generate_property_header(&c_type_name_str, property));
+        }
+    }
+
     if let Some(header_path) = &args.generate_header {
         let mut header_content = String::new();
 
         header_content.push_str("#pragma once\n\n");
         header_content.push_str("#include <glib.h>\n");
         header_content.push_str("#include <gio/gio.h>\n");
-        header_content.push_str("#include <glib-object.h>\n\n");
+        if headers.iter().any(|header| header.contains("GUnixFDList")) {
+            header_content.push_str("#include <gio/gunixfdlist.h>\n");
+        }
+        header_content.push_str("#include <glib-object.h>\n");
+        let mut includes = Vec::new();
+        for type_ref in args.parent.iter().chain(&args.implements) {
+            if let Some(ref header) = type_ref.header {
+                if !includes.contains(header) {
+                    includes.push(header.clone());
+                }
+            }
+        }
+        for include in &includes {
+            header_content.push_str(&format!("#include {}\n", include));
+        }
+        header_content.push('\n');
         header_content.push_str("G_BEGIN_DECLS\n\n");
 
+        if let Some(type_header) = generate_type_header(
+            ffi_type,
+            &c_type_name_str,
+            &prefix,
+            args.parent.as_ref(),
+            &args.implements,
+            &args.fields,
+        ) {
+            header_content.push_str(&type_header);
+        }
+
         match ffi_type {
             types::FfiType::Object | types::FfiType::Shared | types::FfiType::Boxed => {
                 header_content.push_str(&format!(
-                    "typedef struct _{} {};\n\n",
+                    "typedef struct _{} {};\n",
                     c_type_name_str, c_type_name_str
                 ));
+                if !vfunc_headers.is_empty() {
+                    header_content.push_str(&format!(
+                        "typedef struct _{}Class {}Class;\n\nstruct _{} {{\n    {} parent_instance;\n}};\n",
+                        c_type_name_str, c_type_name_str, c_type_name_str, parent_c_type_name
+                    ));
+                }
+                header_content.push('\n');
+            }
+            types::FfiType::Interface => {
+                header_content.push_str(&format!(
+                    "typedef struct _{} {};\n",
+                    c_type_name_str, c_type_name_str
+                ));
+                if !vfunc_headers.is_empty() {
+                    header_content.push_str(&format!(
+                        "typedef struct _{}Interface {}Interface;\n",
+                        c_type_name_str, c_type_name_str
+                    ));
+                }
+                header_content.push('\n');
+            }
+            types::FfiType::Struct => {
+                let fields = args
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "    {} {};\n",
+                            utils::rust_type_to_c_type_string(&field.ty),
+                            field.ident
+                        )
+                    })
+                    .collect::<String>();
+                header_content.push_str(&format!(
+                    "typedef struct _{} {};\n\nstruct _{} {{\n{}}};\n\n",
+                    c_type_name_str, c_type_name_str, c_type_name_str, fields
+                ));
+                header_content.push_str(&record_header_asserts(&c_type_name_str, &args.fields));
             }
             types::FfiType::Enum | types::FfiType::Flags => {}
         }
-        header_content.push_str(&format!("GType {}_get_type(void);\n\n", prefix));
+        header_content.push_str(&generate_type_macros(
+            ffi_type,
+            &args.prefix.value(),
+            &type_name_lower,
+            &prefix,
+            &c_type_name_str,
+            !vfunc_headers.is_empty(),
+        ));
+        header_content.push_str(&kind_header);
 
         for header in &headers {
             header_content.push_str(header);
             header_content.push('\n');
         }
 
+        // After the methods, whose headers declare the callback typedefs
+        // the slots may use
+        if !vfunc_headers.is_empty() {
+            let (fields, docs): (String, String) = vfunc_headers.iter().cloned().unzip();
+            let (vtable, parent, parent_doc) = if ffi_type == types::FfiType::Interface {
+                (
+                    "Interface",
+                    "GTypeInterface parent_iface".to_string(),
+                    "@parent_iface: the parent interface",
+                )
+            } else {
+                (
+                    "Class",
+                    format!("{}Class parent_class", parent_c_type_name),
+                    "@parent_class: the parent class",
+                )
+            };
+            header_content.push_str(&format!(
+                "/**\n * {}{}:\n * {}\n{} */\nstruct _{}{} {{\n    {};\n{}}};\n\n",
+                c_type_name_str, vtable, parent_doc, docs, c_type_name_str, vtable, parent, fields
+            ));
+        }
+
         header_content.push_str("G_END_DECLS\n");
 
         let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
@@ -186,20 +1260,52 @@ pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
         if let ImplItem::Fn(method) = item {
             for arg in &mut method.sig.inputs {
                 if let FnArg::Typed(pat_type) = arg {
-                    pat_type
-                        .attrs
-                        .retain(|attr| !attr.path().is_ident("c_type"));
+                    pat_type.attrs.retain(|attr| {
+                        !attr.path().is_ident("c_type") && !attr.path().is_ident("ffi")
+                    });
                 }
             }
+            method.attrs.retain(|attr| !attr.path().is_ident("ffi"));
         }
     }
 
+    let type_assertions = generate_type_assertions(
+        ffi_type,
+        self_type,
+        args.parent.as_ref(),
+        &args.implements,
+        args.generate_header.is_some() && !vfunc_headers.is_empty(),
+    );
+    let manifest_impl = generate_manifest_impl(ffi_type, self_type, &c_type_name_str, &prefix);
+    let record_impls = if ffi_type == types::FfiType::Struct {
+        generate_record_impls(self_type, &c_type_name_str, &args.fields)
+    } else {
+        quote! {}
+    };
     let type_alias = generate_type_alias(ffi_type, self_type, &c_type_name);
     let get_type_fn = generate_get_type_fn(&prefix, self_type);
+    let lifecycle_fns = generate_lifecycle_fns(ffi_type, &prefix, self_type, &c_type_name);
+    let vtable_struct = if vfunc_slots.is_empty() {
+        quote! {}
+    } else {
+        generate_vtable_struct(
+            ffi_type,
+            self_type,
+            &c_type_name,
+            &vfunc_slots,
+            &vfunc_inits,
+        )
+    };
 
     let expanded = quote! {
         #cleaned_input
 
+        #type_assertions
+
+        #manifest_impl
+
+        #record_impls
+
         pub mod ffi {
             use super::*;
 
@@ -207,6 +1313,10 @@ pub fn ffi_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 
             #get_type_fn
 
+            #lifecycle_fns
+
+            #vtable_struct
+
             #(#ffi_functions)*
         }
     };
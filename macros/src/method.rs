@@ -1,7 +1,23 @@
 use quote::quote;
 use syn::Type;
 
-use crate::types::{FfiType, TransferMode};
+use crate::types::{Collection, DurationUnit, FfiType, TransferMode};
+
+/// Whether a `Duration` marked `#[ffi(duration = millis)]` is passed as
+/// `guint` milliseconds instead of `gint64` microseconds
+fn duration_millis(
+    ty: &Type,
+    ffi_attrs: &crate::types::FfiAttrs,
+    spanned: &dyn quote::ToTokens,
+) -> syn::Result<bool> {
+    match ffi_attrs.duration {
+        Some(_) if !crate::utils::is_duration_type(ty) => Err(syn::Error::new_spanned(
+            spanned,
+            "`duration` requires a `Duration` parameter or return value",
+        )),
+        duration => Ok(duration == Some(DurationUnit::Millis)),
+    }
+}
 
 struct AsyncFunctionNames {
     async_name: syn::Ident,
@@ -19,17 +35,977 @@ impl AsyncFunctionNames {
     }
 }
 
+/// How a `&mut T` parameter is marshalled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutMode {
+    /// Rust fills a default value that is written back with transfer full
+    Out,
+    /// The caller's value is taken over and the updated one written back
+    InOut,
+    /// The caller provides the storage for the Rust value itself
+    CallerAllocates,
+}
+
+impl OutMode {
+    fn to_gir_annotation(self) -> &'static str {
+        match self {
+            OutMode::Out => "out",
+            OutMode::InOut => "inout",
+            OutMode::CallerAllocates => "out caller-allocates",
+        }
+    }
+}
+
+/// A `&str`, `&glib::GStr`, `&CStr`, `&Path` or `&OsStr` parameter, borrowed
+/// from the caller's `const char*` instead of being copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BorrowedStr {
+    Str,
+    GStr,
+    CStr,
+    /// Filename encoded
+    Path,
+    /// Filename encoded
+    OsStr,
+}
+
+impl BorrowedStr {
+    /// Detects the borrowed string types, optionally wrapped in `Option`
+    fn from_type(ty: &Type) -> Option<Self> {
+        let ty = crate::utils::extract_option_inner(ty).unwrap_or(ty);
+        let Type::Reference(r) = ty else {
+            return None;
+        };
+        if r.mutability.is_some() {
+            return None;
+        }
+        let Type::Path(type_path) = &*r.elem else {
+            return None;
+        };
+        match type_path.path.segments.last()?.ident.to_string().as_str() {
+            "str" => Some(BorrowedStr::Str),
+            "GStr" => Some(BorrowedStr::GStr),
+            "CStr" => Some(BorrowedStr::CStr),
+            "Path" => Some(BorrowedStr::Path),
+            "OsStr" => Some(BorrowedStr::OsStr),
+            _ => None,
+        }
+    }
+
+    /// Runtime helper checking the C string and borrowing it
+    fn borrow_fn(self) -> proc_macro2::TokenStream {
+        match self {
+            BorrowedStr::Str => quote! { ::gobject_ffi::strings::str_from_c },
+            BorrowedStr::GStr => quote! { ::gobject_ffi::strings::gstr_from_c },
+            BorrowedStr::CStr => quote! { ::gobject_ffi::strings::cstr_from_c },
+            BorrowedStr::Path => quote! { ::gobject_ffi::strings::path_from_c },
+            BorrowedStr::OsStr => quote! { ::gobject_ffi::strings::os_str_from_c },
+        }
+    }
+}
+
+/// A value marked `#[ffi(variant)]`, passed as a `GVariant*`. Parameters are
+/// type checked before Rust sees them
+struct TypedVariant {
+    /// Type string from `#[ffi(variant = "...")]`
+    type_string: Option<String>,
+    /// The value isn't a `glib::Variant` itself and is converted with
+    /// `FromVariant`, after checking its static variant type
+    converts: bool,
+}
+
+impl TypedVariant {
+    fn new(
+        ty: &Type,
+        ffi_attrs: &crate::types::FfiAttrs,
+        spanned: &dyn quote::ToTokens,
+    ) -> syn::Result<Option<Self>> {
+        let Some(ref type_string) = ffi_attrs.variant else {
+            return Ok(None);
+        };
+
+        let value_type = crate::utils::extract_option_inner(ty).unwrap_or(ty);
+        if matches!(value_type, Type::Reference(_)) {
+            return Err(syn::Error::new_spanned(
+                spanned,
+                "`variant` values are passed by value",
+            ));
+        }
+
+        let converts = crate::utils::last_segment_ident(value_type).as_deref() != Some("Variant");
+        if !converts && type_string.is_none() {
+            return Err(syn::Error::new_spanned(
+                spanned,
+                "`variant` on a `glib::Variant` needs a type string, like `variant = \"a{sv}\"`",
+            ));
+        }
+
+        Ok(Some(TypedVariant {
+            type_string: type_string.as_ref().map(syn::LitStr::value),
+            converts,
+        }))
+    }
+
+    /// Checks and converts the `GVariant*` `param_name` into a value of type
+    /// `ty`, running `on_error` on a mismatch
+    fn generate_from_c(
+        &self,
+        param_name: &syn::Ident,
+        ty: &Type,
+        on_error: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let param_str = param_name.to_string();
+        let optional = crate::utils::extract_option_inner(ty);
+        let value_type = optional.unwrap_or(ty);
+        let from_c = if self.converts {
+            let type_string = match self.type_string {
+                Some(ref type_string) => quote! { Some(#type_string) },
+                None => quote! { None },
+            };
+            quote! {
+                ::gobject_ffi::variant::from_c::<#value_type>(#param_name, #type_string, #param_str)
+            }
+        } else {
+            let type_string = self
+                .type_string
+                .as_ref()
+                .expect("`glib::Variant` values have a type string");
+            quote! { ::gobject_ffi::variant::typed_from_c(#param_name, #type_string, #param_str) }
+        };
+        let checked = if optional.is_some() {
+            quote! { unsafe { #from_c } }
+        } else {
+            quote! {
+                unsafe { #from_c }
+                    .and_then(|value| ::gobject_ffi::variant::required(value, #param_str))
+            }
+        };
+        quote! {
+            let #param_name: #ty = match #checked {
+                Ok(value) => value,
+                Err(err) => #on_error,
+            };
+        }
+    }
+
+    /// Converts the returned value `val` of type `ty` into a `GVariant*`
+    /// owned by the caller, `NULL` for `None`
+    fn generate_to_c(&self, ty: &Type) -> proc_macro2::TokenStream {
+        let to_c = if self.converts {
+            let type_string = match self.type_string {
+                Some(ref type_string) => quote! { Some(#type_string) },
+                None => quote! { None },
+            };
+            quote! { ::gobject_ffi::variant::to_c(&val, #type_string) }
+        } else {
+            let type_string = self
+                .type_string
+                .as_ref()
+                .expect("`glib::Variant` values have a type string");
+            quote! { ::gobject_ffi::variant::typed_to_c(val, #type_string) }
+        };
+        if crate::utils::extract_option_inner(ty).is_some() {
+            quote! {
+                match val {
+                    Some(val) => #to_c,
+                    None => ::std::ptr::null_mut(),
+                }
+            }
+        } else {
+            to_c
+        }
+    }
+
+    /// States the expected type after the C type in the docs, naming the
+    /// Rust type when its type string isn't known until runtime
+    fn doc(&self, ty: &Type) -> String {
+        let value_type = crate::utils::extract_option_inner(ty).unwrap_or(ty);
+        match self
+            .type_string
+            .clone()
+            .or_else(|| crate::utils::variant_type_string(value_type))
+        {
+            Some(type_string) => format!("of type `{}`", type_string),
+            None => format!(
+                "holding a `{}`",
+                quote! { #value_type }.to_string().replace(' ', "")
+            ),
+        }
+    }
+}
+
+/// How long C keeps a callback alive, from the closure's bounds and lifetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallbackScope {
+    /// Borrowed closures, only called during the call
+    Call,
+    /// `'static` `Fn` and `FnMut` closures, kept until Rust drops them and
+    /// calls the `GDestroyNotify`
+    Notified,
+    /// `'static` `FnOnce` closures, called once at some later point
+    Async,
+}
+
+impl CallbackScope {
+    fn to_gir_annotation(self) -> &'static str {
+        match self {
+            CallbackScope::Call => "scope call",
+            CallbackScope::Notified => "scope notified",
+            CallbackScope::Async => "scope async",
+        }
+    }
+}
+
+/// How the Rust method takes the closure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClosureKind {
+    /// `impl Fn(..)`
+    Impl,
+    /// `Box<dyn Fn(..)>`
+    Boxed,
+    /// `&dyn Fn(..)`
+    Ref,
+    /// `&mut dyn FnMut(..)`
+    RefMut,
+}
+
+/// A closure parameter, passed by C as a function pointer of the typedef
+/// `typedef_name`, a `user_data` pointer and, for notified closures, a
+/// `GDestroyNotify`
+struct Closure {
+    kind: ClosureKind,
+    scope: CallbackScope,
+    /// Argument types of the closure
+    args: Vec<Type>,
+    /// Return type of the closure, `None` for `()`
+    output: Option<Type>,
+    /// `Option<..>`, which is `None` when C passes a `NULL` function
+    nullable: bool,
+    /// Bounded by `Send` or `Sync`, so Rust may call it from other threads
+    sendable: bool,
+    /// `#[ffi(thread_safe)]`: the caller's `user_data` may be used from other
+    /// threads
+    thread_safe: bool,
+    typedef_name: String,
+}
+
+impl Closure {
+    /// Detects closure types, optionally wrapped in `Option`
+    fn from_type(ty: &Type) -> Option<Self> {
+        let inner = crate::utils::extract_option_inner(ty);
+        let nullable = inner.is_some();
+        let ty = inner.unwrap_or(ty);
+
+        let (kind, bounds) = match ty {
+            Type::ImplTrait(impl_trait) => (ClosureKind::Impl, &impl_trait.bounds),
+            Type::Reference(r) => {
+                let Type::TraitObject(object) = &*r.elem else {
+                    return None;
+                };
+                let kind = if r.mutability.is_some() {
+                    ClosureKind::RefMut
+                } else {
+                    ClosureKind::Ref
+                };
+                (kind, &object.bounds)
+            }
+            Type::Path(_) if crate::utils::last_segment_ident(ty).as_deref() == Some("Box") => {
+                let Type::Path(type_path) = ty else {
+                    unreachable!()
+                };
+                let syn::PathArguments::AngleBracketed(ref args) =
+                    type_path.path.segments.last()?.arguments
+                else {
+                    return None;
+                };
+                let Some(syn::GenericArgument::Type(Type::TraitObject(object))) = args.args.first()
+                else {
+                    return None;
+                };
+                (ClosureKind::Boxed, &object.bounds)
+            }
+            _ => return None,
+        };
+
+        let (trait_name, fn_args) = bounds.iter().find_map(|bound| {
+            let syn::TypeParamBound::Trait(trait_bound) = bound else {
+                return None;
+            };
+            let segment = trait_bound.path.segments.last()?;
+            let trait_name = segment.ident.to_string();
+            match segment.arguments {
+                syn::PathArguments::Parenthesized(ref fn_args)
+                    if matches!(trait_name.as_str(), "Fn" | "FnMut" | "FnOnce") =>
+                {
+                    Some((trait_name, fn_args))
+                }
+                _ => None,
+            }
+        })?;
+
+        // Boxed trait objects are `'static` unless they name another lifetime
+        let lifetime = bounds.iter().find_map(|bound| match bound {
+            syn::TypeParamBound::Lifetime(lifetime) => Some(lifetime.ident.to_string()),
+            _ => None,
+        });
+        let is_static = match lifetime {
+            Some(lifetime) => lifetime == "static",
+            None => kind == ClosureKind::Boxed,
+        };
+
+        let scope = if !is_static || matches!(kind, ClosureKind::Ref | ClosureKind::RefMut) {
+            CallbackScope::Call
+        } else if trait_name == "FnOnce" {
+            CallbackScope::Async
+        } else {
+            CallbackScope::Notified
+        };
+
+        let output = match fn_args.output {
+            syn::ReturnType::Type(_, ref ty) if !crate::utils::is_unit_type(ty) => {
+                Some((**ty).clone())
+            }
+            _ => None,
+        };
+
+        let sendable = bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(trait_bound) => trait_bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Send" || segment.ident == "Sync"),
+            _ => false,
+        });
+
+        Some(Closure {
+            kind,
+            scope,
+            args: fn_args.inputs.iter().cloned().collect(),
+            output,
+            nullable,
+            sendable,
+            thread_safe: false,
+            typedef_name: String::new(),
+        })
+    }
+
+    /// The owned `FfiConvert` type passed to C for a closure argument, such
+    /// as `String` for `&str` or `T` for `&T`
+    fn owned_arg(ty: &Type) -> Type {
+        match ty {
+            Type::Reference(r) => match crate::utils::last_segment_ident(&r.elem).as_deref() {
+                Some("str") => syn::parse_quote! { ::std::string::String },
+                _ => (*r.elem).clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    fn typedef_ident(&self) -> syn::Ident {
+        syn::Ident::new(&self.typedef_name, proc_macro2::Span::call_site())
+    }
+
+    fn data_ident(param_name: &syn::Ident) -> syn::Ident {
+        quote::format_ident!("{}_data", param_name)
+    }
+
+    fn destroy_ident(&self, param_name: &syn::Ident) -> Option<syn::Ident> {
+        (self.scope == CallbackScope::Notified)
+            .then(|| quote::format_ident!("{}_destroy", param_name))
+    }
+
+    fn closure_ident(param_name: &syn::Ident) -> syn::Ident {
+        quote::format_ident!("ffi_closure_{}", param_name)
+    }
+
+    /// Rust type of the C function pointer
+    fn generate_typedef(&self) -> proc_macro2::TokenStream {
+        let typedef = self.typedef_ident();
+        let args = self.args.iter().map(|arg| {
+            let owned = Self::owned_arg(arg);
+            quote! { <#owned as ::gobject_ffi::FfiConvert>::CType }
+        });
+        let output = self.output.as_ref().map(|output| {
+            quote! { -> <#output as ::gobject_ffi::FfiConvert>::CType }
+        });
+        quote! {
+            pub type #typedef = unsafe extern "C" fn(#(#args,)* ::glib::ffi::gpointer) #output;
+        }
+    }
+
+    fn generate_c_params(
+        &self,
+        param_name: &syn::Ident,
+        c_type: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let data = Self::data_ident(param_name);
+        let destroy = self
+            .destroy_ident(param_name)
+            .map(|destroy| quote! { #destroy: ::glib::ffi::GDestroyNotify, });
+        quote! {
+            #param_name: #c_type,
+            #data: ::glib::ffi::gpointer,
+            #destroy
+        }
+    }
+
+    fn c_param_names(&self, param_name: &syn::Ident) -> Vec<syn::Ident> {
+        let mut names = vec![param_name.clone(), Self::data_ident(param_name)];
+        names.extend(self.destroy_ident(param_name));
+        names
+    }
+
+    /// Wraps the C function pointer `param_name` in a Rust closure. Each
+    /// call lends the converted arguments to C and takes over the result
+    fn generate_from_c(
+        &self,
+        param_name: &syn::Ident,
+        on_error: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let param_str = param_name.to_string();
+        let data = Self::data_ident(param_name);
+        let destroy = match self.destroy_ident(param_name) {
+            Some(destroy) => quote! { #destroy },
+            None => quote! { None },
+        };
+        let user_data = quote::format_ident!("ffi_user_data_{}", param_name);
+        let closure_ident = Self::closure_ident(param_name);
+
+        let arg_names = (0..self.args.len())
+            .map(|i| quote::format_ident!("arg{}", i))
+            .collect::<Vec<_>>();
+        let arg_types = &self.args;
+        let to_c = self.args.iter().zip(&arg_names).map(|(arg, name)| {
+            let owned = Self::owned_arg(arg);
+            let value = if matches!(arg, Type::Reference(_)) {
+                quote! { ::std::borrow::ToOwned::to_owned(#name) }
+            } else {
+                quote! { #name }
+            };
+            quote! {
+                let #name = <#owned as ::gobject_ffi::FfiConvert>::to_c_owned(#value);
+            }
+        });
+        let release = self.args.iter().zip(&arg_names).map(|(arg, name)| {
+            let owned = Self::owned_arg(arg);
+            quote! {
+                drop(unsafe { <#owned as ::gobject_ffi::FfiConvert>::from_c_owned(#name) });
+            }
+        });
+        let (output, result) = match self.output {
+            Some(ref output) => (
+                quote! { -> #output },
+                quote! { unsafe { <#output as ::gobject_ffi::FfiConvert>::from_c_owned(ret) } },
+            ),
+            None => (quote! {}, quote! {}),
+        };
+        let closure = quote! {
+            move |#(#arg_names: #arg_types),*| #output {
+                #(#to_c)*
+                let ret = unsafe { func(#(#arg_names,)* #user_data.as_ptr()) };
+                #(#release)*
+                #result
+            }
+        };
+
+        let mutability = (self.kind == ClosureKind::RefMut).then(|| quote! { mut });
+        let value = if self.nullable {
+            quote! {
+                match #param_name {
+                    Some(func) => Some(#closure),
+                    None => None,
+                }
+            }
+        } else {
+            quote! {{
+                let func = match ::gobject_ffi::callback::required(#param_name, #param_str) {
+                    Ok(func) => func,
+                    Err(err) => #on_error,
+                };
+                #closure
+            }}
+        };
+        let user_data_value = if self.thread_safe {
+            quote! {
+                unsafe {
+                    ::gobject_ffi::callback::UserData::new(#data, #destroy).assume_thread_safe()
+                }
+            }
+        } else {
+            quote! { ::gobject_ffi::callback::UserData::new(#data, #destroy) }
+        };
+        quote! {
+            let #user_data = #user_data_value;
+            let #mutability #closure_ident = #value;
+        }
+    }
+
+    /// Expression passing the wrapped closure of type `ty` to the Rust
+    /// method
+    fn generate_call_arg(&self, param_name: &syn::Ident, ty: &Type) -> proc_macro2::TokenStream {
+        let closure_ident = Self::closure_ident(param_name);
+        let inner = crate::utils::extract_option_inner(ty).unwrap_or(ty);
+        match (self.kind, self.nullable) {
+            (ClosureKind::Impl, _) => quote! { #closure_ident },
+            (ClosureKind::Boxed, false) => quote! { ::std::boxed::Box::new(#closure_ident) },
+            (ClosureKind::Boxed, true) => {
+                quote! { #closure_ident.map(|func| ::std::boxed::Box::new(func) as #inner) }
+            }
+            (ClosureKind::Ref, false) => quote! { &#closure_ident },
+            (ClosureKind::Ref, true) => {
+                quote! { #closure_ident.as_ref().map(|func| func as #inner) }
+            }
+            (ClosureKind::RefMut, false) => quote! { &mut #closure_ident },
+            (ClosureKind::RefMut, true) => {
+                quote! { #closure_ident.as_mut().map(|func| func as #inner) }
+            }
+        }
+    }
+
+    fn to_header_param(&self, param_name: &syn::Ident) -> String {
+        let mut param = format!(
+            "{} {}, gpointer {}",
+            self.typedef_name,
+            param_name,
+            Self::data_ident(param_name)
+        );
+        if let Some(destroy) = self.destroy_ident(param_name) {
+            param.push_str(&format!(", GDestroyNotify {}", destroy));
+        }
+        param
+    }
+
+    fn to_gtk_doc_annotation(&self, param_name: &syn::Ident) -> String {
+        let data = Self::data_ident(param_name);
+        let mut annotations = Vec::new();
+        if self.nullable {
+            annotations.push("nullable".to_string());
+        }
+        annotations.push(self.scope.to_gir_annotation().to_string());
+        annotations.push(format!("closure {}", data));
+        let destroy = self.destroy_ident(param_name);
+        if let Some(ref destroy) = destroy {
+            annotations.push(format!("destroy {}", destroy));
+        }
+
+        let mut doc = format!(
+            " * @{}: ({}) {}\n * @{}: (closure) gpointer",
+            param_name,
+            annotations.join(") ("),
+            self.typedef_name,
+            data
+        );
+        if self.thread_safe {
+            doc.push_str(&format!(
+                ", which must be thread-safe: @{} and its destroy notify may be called from any thread",
+                param_name
+            ));
+        }
+        if let Some(destroy) = destroy {
+            doc.push_str(&format!(
+                "\n * @{}: (nullable) GDestroyNotify, called on @{} once @{} is no longer used",
+                destroy, data, param_name
+            ));
+        }
+        doc
+    }
+
+    /// Documented C typedef of the callback, declared before the method
+    fn generate_header_typedef(&self) -> String {
+        let arg_names = (0..self.args.len())
+            .map(|i| format!("arg{}", i))
+            .collect::<Vec<_>>();
+        let arg_types = self
+            .args
+            .iter()
+            .map(|arg| crate::utils::rust_type_to_c_type_string(&Self::owned_arg(arg)))
+            .collect::<Vec<_>>();
+
+        let mut doc = format!("/**\n * {}:\n", self.typedef_name);
+        for (name, c_type) in arg_names.iter().zip(&arg_types) {
+            doc.push_str(&format!(" * @{}: {}\n", name, c_type));
+        }
+        doc.push_str(" * @user_data: (closure) the data passed with the callback\n");
+        let return_type = match self.output {
+            Some(ref output) => {
+                let transfer = if crate::utils::is_primitive_type(output) {
+                    ""
+                } else {
+                    " (transfer full)"
+                };
+                doc.push_str(&format!(" *\n * Returns:{} the result\n", transfer));
+                crate::utils::rust_type_to_c_type_string(output)
+            }
+            None => "void".to_string(),
+        };
+        doc.push_str(" */\n");
+
+        let params = arg_types
+            .iter()
+            .zip(&arg_names)
+            .map(|(c_type, name)| format!("{} {}", c_type, name))
+            .chain(std::iter::once("gpointer user_data".to_string()))
+            .collect::<Vec<_>>();
+        doc.push_str(&format!(
+            "typedef {} (*{})({});\n\n",
+            return_type,
+            self.typedef_name,
+            params.join(", ")
+        ));
+        doc
+    }
+}
+
+/// Converts the C value of parameter `param_name` with one of the checked
+/// `FfiConvert` conversions, running `on_error` for values such as `NULL`
+fn checked_from_c(
+    ty: &Type,
+    convert: proc_macro2::TokenStream,
+    param_name: &syn::Ident,
+    on_error: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let param_str = param_name.to_string();
+    quote! {
+        match unsafe { <#ty as ::gobject_ffi::FfiConvert>::#convert(#param_name, #param_str) } {
+            Ok(value) => value,
+            Err(err) => #on_error,
+        }
+    }
+}
+
+/// A slice or `Vec` marshalled as a C array of `FfiConvert` elements, or as
+/// a `GList`/`GPtrArray` of GLib pointer types
+struct CArray {
+    elem: Type,
+    /// `&[T]`, borrowed from the C array or from a `Vec` converted from it
+    is_slice: bool,
+    /// `Option<..>`, where `NULL` is `None`
+    nullable: bool,
+    transfer: TransferMode,
+    zero_terminated: bool,
+    collection: Collection,
+    /// Slice borrowed straight from the C array instead of converted
+    borrowed: bool,
+}
+
+impl CArray {
+    fn from_type(
+        ty: &Type,
+        ffi_attrs: &crate::types::FfiAttrs,
+        default_transfer: TransferMode,
+    ) -> Option<Self> {
+        let (inner, nullable) = match crate::utils::extract_option_inner(ty) {
+            Some(inner) => (inner, true),
+            None => (ty, false),
+        };
+        let (elem, is_slice) = crate::utils::extract_array_elem(inner)?;
+        let collection = ffi_attrs.collection.unwrap_or(Collection::Array);
+
+        Some(Self {
+            elem: elem.clone(),
+            is_slice,
+            nullable,
+            transfer: ffi_attrs.transfer.unwrap_or(default_transfer),
+            // String arrays are `GStrv` unless a length is asked for
+            zero_terminated: collection == Collection::Array
+                && ffi_attrs
+                    .zero_terminated
+                    .unwrap_or_else(|| crate::utils::is_string_type(elem)),
+            collection,
+            borrowed: false,
+        })
+    }
+
+    /// Whether a slice can point into the caller's array, which holds for
+    /// numbers whose C representation is the Rust one, such as `&[u8]`
+    fn can_borrow(&self) -> bool {
+        self.is_slice
+            && self.collection == Collection::Array
+            && self.transfer == TransferMode::None
+            && !self.zero_terminated
+            && crate::utils::is_primitive_type(&self.elem)
+            && !matches!(&self.elem, Type::Path(p) if p.path.is_ident("bool"))
+    }
+
+    /// Whether the elements are `&str`, borrowed from converted `String`s
+    fn is_str_refs(&self) -> bool {
+        crate::utils::is_str_ref(&self.elem)
+    }
+
+    /// The owned element type converted from C
+    fn owned_elem(&self) -> Type {
+        if self.is_str_refs() {
+            syn::parse_quote! { ::std::string::String }
+        } else {
+            self.elem.clone()
+        }
+    }
+
+    /// The same array under another ownership transfer
+    fn with_transfer(&self, transfer: TransferMode) -> Self {
+        Self {
+            elem: self.elem.clone(),
+            transfer,
+            borrowed: false,
+            ..*self
+        }
+    }
+
+    fn has_length(&self) -> bool {
+        self.collection == Collection::Array && !self.zero_terminated
+    }
+
+    /// C type of the array pointer, `const` for borrowed C arrays
+    fn c_type(&self) -> proc_macro2::TokenStream {
+        let elem = self.owned_elem();
+        match self.collection {
+            Collection::Array if self.transfer == TransferMode::None => {
+                quote! { *const <#elem as ::gobject_ffi::FfiConvert>::CType }
+            }
+            Collection::Array => quote! { *mut <#elem as ::gobject_ffi::FfiConvert>::CType },
+            Collection::List => quote! { *mut ::glib::ffi::GList },
+            Collection::PtrArray => quote! { *mut ::glib::ffi::GPtrArray },
+        }
+    }
+
+    /// Converts the C array at `data` into a `Vec`, or an `Option<Vec>` for
+    /// nullable arrays
+    fn generate_from_c(
+        &self,
+        data: proc_macro2::TokenStream,
+        len: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let elem = self.owned_elem();
+        let values = if self.borrowed {
+            quote! {
+                unsafe { ::gobject_ffi::array::borrow::<#elem>(ffi_data, #len) }
+            }
+        } else if self.collection == Collection::Array {
+            let convert = match self.transfer {
+                TransferMode::Primitive | TransferMode::None => quote! { from_c_none },
+                TransferMode::Container => quote! { from_c_container },
+                TransferMode::Full => quote! { from_c_full },
+            };
+            let len = if self.zero_terminated {
+                quote! { unsafe { ::gobject_ffi::array::zero_terminated_len(ffi_data) } }
+            } else {
+                len
+            };
+            quote! {
+                unsafe { ::gobject_ffi::array::#convert::<#elem>(ffi_data, #len) }
+            }
+        } else {
+            let convert = match self.transfer {
+                TransferMode::Primitive | TransferMode::None => quote! { from_glib_none },
+                TransferMode::Container => quote! { from_glib_container },
+                TransferMode::Full => quote! { from_glib_full },
+            };
+            quote! {
+                unsafe {
+                    <::std::vec::Vec<#elem> as ::glib::translate::FromGlibPtrContainer<_, _>>::#convert(ffi_data)
+                }
+            }
+        };
+
+        if self.nullable {
+            quote! {
+                {
+                    let ffi_data = #data;
+                    if ffi_data.is_null() { None } else { Some(#values) }
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let ffi_data = #data;
+                    #values
+                }
+            }
+        }
+    }
+
+    /// Transfers `values` to a new C array, storing its length in `length`
+    /// unless the array is zero-terminated. Returned `transfer none` and
+    /// `transfer container` arrays are kept on `self_param` under `key`
+    fn generate_to_c(
+        &self,
+        values: proc_macro2::TokenStream,
+        length: Option<&syn::Ident>,
+        key: &str,
+    ) -> proc_macro2::TokenStream {
+        let elem = &self.elem;
+        let zero_terminated = self.zero_terminated;
+        let store_length = |len: proc_macro2::TokenStream| match length {
+            Some(length) => quote! {
+                if !#length.is_null() {
+                    unsafe {
+                        *#length = #len;
+                    }
+                }
+            },
+            None => quote! {},
+        };
+        let set_length = store_length(quote! { len });
+        let len_binding = if length.is_some() {
+            quote! { len }
+        } else {
+            quote! { _ }
+        };
+        let object = quote! {
+            ::glib::object::Cast::upcast_ref::<::glib::Object>(&self_param)
+        };
+        let convert = match self.collection {
+            Collection::Array => {
+                let to_c = match self.transfer {
+                    TransferMode::Primitive | TransferMode::Full => quote! {
+                        ::gobject_ffi::array::to_c_full::<#elem>(values, #zero_terminated)
+                    },
+                    TransferMode::None => quote! {
+                        ::gobject_ffi::array::to_c_none::<#elem>(#object, #key, values, #zero_terminated)
+                    },
+                    TransferMode::Container => quote! {
+                        ::gobject_ffi::array::to_c_container::<#elem>(#object, #key, values, #zero_terminated)
+                    },
+                };
+                quote! {
+                    {
+                        let (data, #len_binding) = #to_c;
+                        #set_length
+                        data
+                    }
+                }
+            }
+            Collection::List | Collection::PtrArray => {
+                let (c_type, copy) = match self.collection {
+                    Collection::List => (
+                        quote! { ::glib::ffi::GList },
+                        quote! { ::glib::ffi::g_list_copy },
+                    ),
+                    _ => (
+                        quote! { ::glib::ffi::GPtrArray },
+                        quote! { ::gobject_ffi::array::ptr_array_copy },
+                    ),
+                };
+                let full = quote! {
+                    <#elem as ::glib::translate::ToGlibContainerFromSlice<'_, *mut #c_type>>::to_glib_full_from_slice(&values)
+                };
+                let kept = quote! {
+                    ::gobject_ffi::array::keep(#object, #key, #full, |data: *mut #c_type| {
+                        drop(unsafe {
+                            <::std::vec::Vec<#elem> as ::glib::translate::FromGlibPtrContainer<_, _>>::from_glib_full(data)
+                        })
+                    })
+                };
+                match self.transfer {
+                    TransferMode::Primitive | TransferMode::Full => full,
+                    TransferMode::None => kept,
+                    TransferMode::Container => quote! { unsafe { #copy(#kept) } },
+                }
+            }
+        };
+
+        if self.nullable {
+            let clear_length = store_length(quote! { 0 });
+            quote! {
+                match #values {
+                    Some(values) => #convert,
+                    None => {
+                        #clear_length
+                        ::std::ptr::null_mut()
+                    }
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let values = #values;
+                    #convert
+                }
+            }
+        }
+    }
+
+    fn gir_annotations(&self, length: &str) -> Vec<String> {
+        let element_type = format!(
+            "element-type {}",
+            crate::utils::gir_element_type(&self.elem)
+        );
+        match self.collection {
+            Collection::Array if self.zero_terminated => {
+                vec!["array zero-terminated=1".to_string(), element_type]
+            }
+            Collection::Array => vec![format!("array length={}", length), element_type],
+            Collection::List | Collection::PtrArray => vec![element_type],
+        }
+    }
+
+    /// C type of the array pointer, `const` for borrowed scalar elements
+    fn header_c_type(&self) -> String {
+        match self.collection {
+            Collection::Array => {
+                let elem = crate::utils::rust_type_to_c_type_string(&self.elem);
+                if self.transfer == TransferMode::None && !elem.ends_with('*') {
+                    format!("const {}*", elem)
+                } else {
+                    format!("{}*", elem)
+                }
+            }
+            Collection::List => "GList*".to_string(),
+            Collection::PtrArray => "GPtrArray*".to_string(),
+        }
+    }
+}
+
 struct FfiParam {
     name: syn::Ident,
     rust_type: Type,
     c_type: proc_macro2::TokenStream,
     c_type_override: Option<crate::types::CTypeOverride>,
+    /// Inner type of an `Option<primitive>` passed as a nullable pointer
+    nullable_inner: Option<Type>,
+    out_mode: Option<OutMode>,
+    /// `Option<&mut T>`, which is `None` when C passes `NULL`
+    optional_out: bool,
+    /// Slice or `Vec` passed as a C array, behind `&mut` for out parameters
+    array: Option<CArray>,
+    /// `#[ffi(transfer = full)]` on a parameter converted with `FfiConvert`,
+    /// whose C value is taken over
+    owned: bool,
+    borrowed_str: Option<BorrowedStr>,
+    /// Async methods outlive the caller's strings, so borrowed strings are
+    /// copied before the call is scheduled
+    copies_borrowed_str: bool,
+    /// `T` of a `&T` or `Option<&T>` parameter, passed a reference to the
+    /// value converted with `FfiConvert`
+    ref_inner: Option<Type>,
+    /// `Duration` passed as `guint` milliseconds
+    millis: bool,
+    variant: Option<TypedVariant>,
+    closure: Option<Closure>,
+    /// `&mut` number of an async method, updated in the caller's storage
+    /// when the operation runs
+    in_place: bool,
 }
 
 impl FfiParam {
-    fn extract_from_method(method: &syn::ImplItemFn, skip_self: bool) -> syn::Result<Vec<Self>> {
+    fn extract_from_method(
+        method: &syn::ImplItemFn,
+        skip_self: bool,
+        c_type_name: &syn::Ident,
+    ) -> syn::Result<Vec<Self>> {
+        use heck::ToUpperCamelCase;
         use syn::{FnArg, Pat};
 
+        let is_async = method.sig.asyncness.is_some();
+        let method_name = method.sig.ident.to_string().to_upper_camel_case();
+        // Callback typedefs are named after the method, and after the
+        // parameter too when there are several
+        let closure_count = method
+            .sig
+            .inputs
+            .iter()
+            .filter(|arg| matches!(arg, FnArg::Typed(pat_type) if Closure::from_type(&pat_type.ty).is_some()))
+            .count();
         let mut params = Vec::new();
         for arg in &method.sig.inputs {
             match arg {
@@ -37,79 +1013,831 @@ impl FfiParam {
                     if skip_self {
                         continue;
                     } else {
-                        unreachable!("Constructor should not have self");
+                        unreachable!("Constructor should not have self");
+                    }
+                }
+                FnArg::Typed(pat_type) => {
+                    if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                        let param_name = &pat_ident.ident;
+                        let rust_type = (*pat_type.ty).clone();
+                        let c_type_override = crate::utils::extract_c_type(&pat_type.attrs)?;
+                        let ffi_attrs = crate::utils::extract_ffi_attrs(&pat_type.attrs)?;
+                        if let Some(ref error_value) = ffi_attrs.error_value {
+                            return Err(syn::Error::new_spanned(
+                                error_value,
+                                "`error_value` is the C value returned on failure, put it on the method instead",
+                            ));
+                        }
+                        if ffi_attrs.out_names.is_some()
+                            || ffi_attrs.return_first
+                            || ffi_attrs.signal.is_some()
+                            || ffi_attrs.vfunc
+                        {
+                            return Err(syn::Error::new_spanned(
+                                pat_type,
+                                "`out_names`, `return_first`, `signal` and `vfunc` apply to methods, not parameters",
+                            ));
+                        }
+
+                        if let Some(mut closure) = Closure::from_type(&rust_type) {
+                            if is_async && closure.scope == CallbackScope::Call {
+                                return Err(syn::Error::new_spanned(
+                                    pat_type,
+                                    "async methods outlive borrowed closures, take an `impl Fn(..) + 'static` or a `Box<dyn Fn(..)>` instead",
+                                ));
+                            }
+                            if closure.sendable && !ffi_attrs.thread_safe {
+                                return Err(syn::Error::new_spanned(
+                                    pat_type,
+                                    "`Send` and `Sync` closures may use the caller's `user_data` from other threads, mark the parameter `#[ffi(thread_safe)]` to require that in the header",
+                                ));
+                            }
+                            closure.thread_safe = ffi_attrs.thread_safe;
+                            closure.typedef_name = if closure_count > 1 {
+                                format!(
+                                    "{}{}{}Func",
+                                    c_type_name,
+                                    method_name,
+                                    param_name.to_string().to_upper_camel_case()
+                                )
+                            } else {
+                                format!("{}{}Func", c_type_name, method_name)
+                            };
+                            let typedef = closure.typedef_ident();
+                            params.push(Self {
+                                name: param_name.clone(),
+                                c_type: quote! { ::std::option::Option<#typedef> },
+                                rust_type,
+                                c_type_override: None,
+                                nullable_inner: None,
+                                out_mode: None,
+                                optional_out: false,
+                                owned: false,
+                                array: None,
+                                borrowed_str: None,
+                                copies_borrowed_str: false,
+                                ref_inner: None,
+                                millis: false,
+                                variant: None,
+                                closure: Some(closure),
+                                in_place: false,
+                            });
+                            continue;
+                        }
+
+                        let variant = TypedVariant::new(&rust_type, &ffi_attrs, pat_type)?;
+                        let nullable_inner = if c_type_override.is_none()
+                            && !ffi_attrs.sentinel
+                            && variant.is_none()
+                        {
+                            crate::utils::extract_nullable_primitive(&rust_type).cloned()
+                        } else {
+                            None
+                        };
+
+                        let optional_out = crate::utils::extract_option_inner(&rust_type)
+                            .is_some_and(crate::utils::is_mutable_reference);
+                        let mut_ref_type = if optional_out {
+                            crate::utils::extract_option_inner(&rust_type)
+                                .expect("optional out parameters are options")
+                        } else {
+                            &rust_type
+                        };
+                        let out_mode = Self::out_mode(mut_ref_type, &ffi_attrs, pat_type)?;
+                        // Async methods update numbers in the caller's storage,
+                        // which must outlive the operation. Converted values
+                        // have nowhere to be written once the call returned
+                        let in_place = is_async && out_mode == Some(OutMode::InOut) && {
+                            let inner = crate::utils::extract_mut_ref_inner(mut_ref_type)
+                                .expect("inout parameters are mutable references");
+                            crate::utils::is_primitive_type(inner)
+                                && !matches!(inner, Type::Path(p) if p.path.is_ident("bool"))
+                        };
+                        if is_async && out_mode.is_some() && !in_place {
+                            return Err(syn::Error::new_spanned(
+                                pat_type,
+                                "async methods can only update `&mut` numbers in place; return other values instead",
+                            ));
+                        }
+                        let mut array = if c_type_override.is_some() || variant.is_some() {
+                            None
+                        } else {
+                            Self::array(mut_ref_type, out_mode, &ffi_attrs, pat_type)?
+                        };
+                        if let Some(ref mut array) = array {
+                            // Async calls outlive the caller's array
+                            array.borrowed = out_mode.is_none() && !is_async && array.can_borrow();
+                        }
+
+                        let value_type =
+                            crate::utils::extract_option_inner(&rust_type).unwrap_or(&rust_type);
+                        if is_async
+                            && crate::utils::last_segment_ident(value_type).as_deref()
+                                == Some("BorrowedFd")
+                        {
+                            return Err(syn::Error::new_spanned(
+                                pat_type,
+                                "`BorrowedFd` is only valid during the call, async methods take an `OwnedFd` instead",
+                            ));
+                        }
+
+                        let borrowed_str = if c_type_override.is_some() {
+                            None
+                        } else {
+                            BorrowedStr::from_type(&rust_type)
+                        };
+
+                        let ref_inner = if c_type_override.is_none()
+                            && borrowed_str.is_none()
+                            && array.is_none()
+                        {
+                            let ty = crate::utils::extract_option_inner(&rust_type)
+                                .unwrap_or(&rust_type);
+                            match ty {
+                                Type::Reference(r) if r.mutability.is_none() => {
+                                    Some((*r.elem).clone())
+                                }
+                                _ => None,
+                            }
+                        } else {
+                            None
+                        };
+
+                        let millis = duration_millis(&rust_type, &ffi_attrs, pat_type)?;
+
+                        let c_type = if let Some(ref override_) = c_type_override {
+                            let c = &override_.c_type;
+                            quote! { #c }
+                        } else if millis {
+                            quote! {
+                                <::gobject_ffi::time::Milliseconds<#rust_type> as ::gobject_ffi::FfiConvert>::CType
+                            }
+                        } else if variant.is_some() {
+                            quote! { *mut ::glib::ffi::GVariant }
+                        } else if borrowed_str.is_some() {
+                            quote! { *const ::std::os::raw::c_char }
+                        } else if let Some(ref array) = array {
+                            let array_c_type = array.c_type();
+                            if out_mode.is_some() {
+                                quote! { *mut #array_c_type }
+                            } else {
+                                array_c_type
+                            }
+                        } else if let Some(ref inner) = nullable_inner {
+                            quote! { *const <#inner as ::gobject_ffi::FfiConvert>::CType }
+                        } else if out_mode == Some(OutMode::CallerAllocates) {
+                            let inner = crate::utils::extract_mut_ref_inner(mut_ref_type)
+                                .expect("caller-allocates parameters are mutable references");
+                            quote! { *mut #inner }
+                        } else {
+                            crate::utils::rust_type_to_c_type(mut_ref_type)
+                        };
+
+                        // `OwnedFd` is taken over unless it's behind a reference
+                        let owned = (ffi_attrs.transfer == Some(TransferMode::Full)
+                            || crate::utils::fd_ownership(&rust_type)
+                                == Some(crate::utils::FdOwnership::Owned))
+                            && array.is_none()
+                            && out_mode.is_none()
+                            && ref_inner.is_none();
+
+                        params.push(Self {
+                            name: param_name.clone(),
+                            rust_type,
+                            c_type,
+                            c_type_override,
+                            nullable_inner,
+                            out_mode,
+                            optional_out,
+                            owned,
+                            array,
+                            borrowed_str,
+                            copies_borrowed_str: is_async,
+                            ref_inner,
+                            millis,
+                            variant,
+                            closure: None,
+                            in_place,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(params)
+    }
+
+    /// Array marshalling for `&[T]` and `Vec<T>` parameters, and for
+    /// `&mut Vec<T>` out parameters which are always `transfer full`
+    fn array(
+        mut_ref_type: &Type,
+        out_mode: Option<OutMode>,
+        ffi_attrs: &crate::types::FfiAttrs,
+        pat_type: &syn::PatType,
+    ) -> syn::Result<Option<CArray>> {
+        if out_mode.is_some()
+            && ffi_attrs
+                .transfer
+                .is_some_and(|transfer| transfer != TransferMode::Full)
+        {
+            return Err(syn::Error::new_spanned(
+                pat_type,
+                "out parameters are always `transfer full`",
+            ));
+        }
+
+        let array = match out_mode {
+            None => CArray::from_type(mut_ref_type, ffi_attrs, TransferMode::None),
+            Some(out_mode) => {
+                let inner = crate::utils::extract_mut_ref_inner(mut_ref_type)
+                    .expect("out parameters are mutable references");
+                let array = CArray::from_type(inner, ffi_attrs, TransferMode::Full);
+                if array.is_some() && out_mode == OutMode::CallerAllocates {
+                    return Err(syn::Error::new_spanned(
+                        pat_type,
+                        "`caller_allocates` is not supported for arrays",
+                    ));
+                }
+                array
+            }
+        };
+
+        // Borrowed descriptors would have to be duplicated, which can fail
+        // for each element
+        if let Some(ref array) = array {
+            if array.transfer != TransferMode::Full
+                && crate::utils::fd_ownership(&array.elem) == Some(crate::utils::FdOwnership::Owned)
+            {
+                return Err(syn::Error::new_spanned(
+                    pat_type,
+                    "arrays of `OwnedFd` must be `transfer = full`, borrow them as `RawFd` instead",
+                ));
+            }
+        }
+
+        if array.is_none()
+            && (ffi_attrs.transfer == Some(TransferMode::Container)
+                || ffi_attrs.zero_terminated.is_some()
+                || ffi_attrs.collection.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                pat_type,
+                "`transfer = container`, `zero_terminated` and `collection` require a slice or `Vec` parameter",
+            ));
+        }
+
+        Ok(array)
+    }
+
+    /// How a `&mut T` parameter is passed: numbers are read-write like
+    /// `gint *value` in C unless marked `out`, other values are written only
+    /// unless marked `inout`
+    fn out_mode(
+        mut_ref_type: &Type,
+        ffi_attrs: &crate::types::FfiAttrs,
+        pat_type: &syn::PatType,
+    ) -> syn::Result<Option<OutMode>> {
+        let Some(inner) = crate::utils::extract_mut_ref_inner(mut_ref_type) else {
+            if ffi_attrs.out || ffi_attrs.inout || ffi_attrs.caller_allocates {
+                return Err(syn::Error::new_spanned(
+                    pat_type,
+                    "`out`, `inout` and `caller_allocates` require a `&mut T` parameter",
+                ));
+            }
+            return Ok(None);
+        };
+
+        Ok(Some(
+            match (ffi_attrs.out, ffi_attrs.inout, ffi_attrs.caller_allocates) {
+                (false, false, false) if crate::utils::is_primitive_type(inner) => OutMode::InOut,
+                (_, false, false) => OutMode::Out,
+                (false, true, false) => OutMode::InOut,
+                (false, false, true) => OutMode::CallerAllocates,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        pat_type,
+                        "`out`, `inout` and `caller_allocates` are mutually exclusive",
+                    ));
+                }
+            },
+        ))
+    }
+
+    /// `T` of a `&mut T` or `Option<&mut T>` parameter
+    fn mut_ref_inner(&self) -> Option<&Type> {
+        let ty = if self.optional_out {
+            crate::utils::extract_option_inner(&self.rust_type)?
+        } else {
+            &self.rust_type
+        };
+        crate::utils::extract_mut_ref_inner(ty)
+    }
+
+    /// The Rust value behind a `&mut T` that is converted and written back
+    /// to C after the call, as opposed to caller-allocated values which are
+    /// borrowed in place
+    fn out_value_type(&self) -> Option<&Type> {
+        let inner = self.mut_ref_inner()?;
+        if self.c_type_override.is_some()
+            || self.in_place
+            || self.out_mode == Some(OutMode::CallerAllocates)
+        {
+            None
+        } else {
+            Some(inner)
+        }
+    }
+
+    fn out_value_ident(&self) -> syn::Ident {
+        quote::format_ident!("ffi_out_{}", self.name)
+    }
+
+    /// The `Vec` a slice parameter borrows from
+    fn array_value_ident(&self) -> syn::Ident {
+        quote::format_ident!("ffi_array_{}", self.name)
+    }
+
+    /// The value a `&T` parameter borrows
+    fn ref_value_ident(&self) -> syn::Ident {
+        quote::format_ident!("ffi_ref_{}", self.name)
+    }
+
+    /// The owned copy of a borrowed string made for async methods
+    fn str_copy_ident(&self) -> syn::Ident {
+        quote::format_ident!("ffi_str_{}", self.name)
+    }
+
+    /// The C parameter carrying the length of an array
+    fn length_ident(&self) -> Option<syn::Ident> {
+        match self.array {
+            Some(ref array) if array.has_length() => Some(quote::format_ident!("n_{}", self.name)),
+            _ => None,
+        }
+    }
+
+    fn generate_c_params(&self) -> proc_macro2::TokenStream {
+        if let Some(ref closure) = self.closure {
+            return closure.generate_c_params(&self.name, &self.c_type);
+        }
+
+        let name = &self.name;
+        let c_type = &self.c_type;
+        match self.length_ident() {
+            Some(length) if self.out_mode.is_some() => {
+                quote! { #name: #c_type, #length: *mut usize, }
+            }
+            Some(length) => quote! { #name: #c_type, #length: usize, },
+            None => quote! { #name: #c_type, },
+        }
+    }
+
+    /// Names of the C parameters, as forwarded by the async sync wrapper
+    fn c_param_names(&self) -> Vec<syn::Ident> {
+        if let Some(ref closure) = self.closure {
+            return closure.c_param_names(&self.name);
+        }
+
+        let mut names = vec![self.name.clone()];
+        names.extend(self.length_ident());
+        names
+    }
+
+    /// Expression passed to the Rust method for this parameter
+    fn generate_call_arg(&self) -> proc_macro2::TokenStream {
+        if let Some(ref closure) = self.closure {
+            closure.generate_call_arg(&self.name, &self.rust_type)
+        } else if self.out_value_type().is_some() {
+            let out_value = self.out_value_ident();
+            if self.optional_out {
+                quote! { #out_value.as_mut() }
+            } else {
+                quote! { &mut #out_value }
+            }
+        } else if self.ref_inner.is_some() {
+            let ref_value = self.ref_value_ident();
+            if crate::utils::extract_option_inner(&self.rust_type).is_some() {
+                quote! { #ref_value.as_ref() }
+            } else {
+                quote! { &#ref_value }
+            }
+        } else if self.borrowed_str.is_some() && self.copies_borrowed_str {
+            let str_copy = self.str_copy_ident();
+            if crate::utils::extract_option_inner(&self.rust_type).is_some() {
+                quote! { #str_copy.as_ref().map(::std::borrow::Borrow::borrow) }
+            } else {
+                quote! { ::std::borrow::Borrow::borrow(&#str_copy) }
+            }
+        } else if let Some(ref array) = self.array {
+            let array_value = self.array_value_ident();
+            if !array.is_slice {
+                if array.is_str_refs() {
+                    quote! { #array_value }
+                } else {
+                    let name = &self.name;
+                    quote! { #name }
+                }
+            } else if array.nullable {
+                quote! { #array_value.as_deref() }
+            } else {
+                quote! { &#array_value }
+            }
+        } else {
+            let name = &self.name;
+            quote! { #name }
+        }
+    }
+
+    /// Converts the Rust value of an out parameter and stores it in the C
+    /// location once the call succeeded
+    fn generate_write_back(&self) -> Option<proc_macro2::TokenStream> {
+        let inner = self.out_value_type()?;
+        let param_name = &self.name;
+        let out_value = self.out_value_ident();
+        if let Some(ref array) = self.array {
+            let length = self.length_ident();
+            let to_c = array.generate_to_c(quote! { #out_value }, length.as_ref(), "");
+            // The `(inout)` array given by the caller is released only now,
+            // before its length is overwritten
+            let free_in = if self.out_mode == Some(OutMode::InOut) {
+                let len = match length {
+                    Some(ref length) => quote! { unsafe { *#length } },
+                    None => quote! { 0 },
+                };
+                let values = array.generate_from_c(quote! { unsafe { *#param_name } }, len);
+                quote! { drop(#values); }
+            } else {
+                quote! {}
+            };
+            let store = quote! {
+                #free_in
+                unsafe {
+                    *#param_name = #to_c;
+                }
+            };
+            return Some(if self.optional_out {
+                quote! {
+                    if let Some(#out_value) = #out_value {
+                        #store
+                    }
+                }
+            } else {
+                quote! {
+                    if !#param_name.is_null() {
+                        #store
+                    }
+                }
+            });
+        }
+        let store = if self.out_mode == Some(OutMode::InOut) {
+            quote! { ::gobject_ffi::inout_to_c::<#inner>(#param_name, #out_value) }
+        } else {
+            quote! { *#param_name = <#inner as ::gobject_ffi::FfiConvert>::to_c_owned(#out_value) }
+        };
+        if self.optional_out {
+            Some(quote! {
+                if let Some(#out_value) = #out_value {
+                    unsafe {
+                        #store;
+                    }
+                }
+            })
+        } else {
+            Some(quote! {
+                if !#param_name.is_null() {
+                    unsafe {
+                        #store;
+                    }
+                }
+            })
+        }
+    }
+
+    /// Converts the C value into the Rust argument. `on_error` runs with the
+    /// `glib::Error` of a rejected borrowed string as `err` and must return
+    fn generate_conversion(&self, on_error: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let param_name = &self.name;
+        let ty = &self.rust_type;
+
+        if let Some(borrowed_str) = self.borrowed_str {
+            let from_c = borrowed_str.borrow_fn();
+            let param_str = param_name.to_string();
+            let checked = quote! {
+                match unsafe { #from_c(#param_name, #param_str) } {
+                    Ok(value) => value,
+                    Err(err) => #on_error,
+                }
+            };
+            let (binding, value) = if self.copies_borrowed_str {
+                (
+                    self.str_copy_ident(),
+                    quote! { ::std::borrow::ToOwned::to_owned(#checked) },
+                )
+            } else {
+                (param_name.clone(), checked)
+            };
+            return if crate::utils::extract_option_inner(ty).is_some() {
+                quote! {
+                    let #binding = if #param_name.is_null() {
+                        None
+                    } else {
+                        Some(#value)
+                    };
+                }
+            } else {
+                quote! {
+                    let #binding = #value;
+                }
+            };
+        }
+
+        if let Some(ref variant) = self.variant {
+            return variant.generate_from_c(param_name, ty, on_error);
+        }
+
+        if let Some(ref closure) = self.closure {
+            return closure.generate_from_c(param_name, on_error);
+        }
+
+        if let Some(inner) = self.out_value_type() {
+            let out_value = self.out_value_ident();
+            let param_str = param_name.to_string();
+            // The caller keeps its `(inout)` value until it's replaced after
+            // a successful call, so it's only borrowed here
+            let initial_value = if self.out_mode != Some(OutMode::InOut) {
+                quote! { ::std::default::Default::default() }
+            } else if let Some(ref array) = self.array {
+                let (len, length_is_null) = match self.length_ident() {
+                    Some(length) => (
+                        quote! { unsafe { *#length } },
+                        quote! { || #length.is_null() },
+                    ),
+                    None => (quote! { 0 }, quote! {}),
+                };
+                let values = array
+                    .with_transfer(TransferMode::None)
+                    .generate_from_c(quote! { unsafe { *#param_name } }, len);
+                quote! {
+                    if #param_name.is_null() #length_is_null {
+                        let err = ::gobject_ffi::strings::null_error(#param_str);
+                        #on_error
+                    } else {
+                        #values
                     }
                 }
-                FnArg::Typed(pat_type) => {
-                    if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                        let param_name = &pat_ident.ident;
-                        let rust_type = (*pat_type.ty).clone();
-                        let c_type_override = crate::utils::extract_c_type(&pat_type.attrs)?;
-
-                        let c_type = if let Some(ref override_) = c_type_override {
-                            let c = &override_.c_type;
-                            quote! { #c }
-                        } else {
-                            crate::utils::rust_type_to_c_type(&rust_type)
-                        };
-
-                        params.push(Self {
-                            name: param_name.clone(),
-                            rust_type,
-                            c_type,
-                            c_type_override,
-                        });
+            } else {
+                quote! {
+                    match unsafe { ::gobject_ffi::inout_from_c::<#inner>(#param_name, #param_str) } {
+                        Ok(value) => value,
+                        Err(err) => #on_error,
                     }
                 }
-            }
+            };
+            return if self.optional_out {
+                quote! {
+                    let mut #out_value: ::std::option::Option<#inner> = if #param_name.is_null() {
+                        None
+                    } else {
+                        Some(#initial_value)
+                    };
+                }
+            } else {
+                quote! {
+                    let mut #out_value: #inner = #initial_value;
+                }
+            };
         }
-        Ok(params)
-    }
 
-    fn generate_conversion(&self) -> proc_macro2::TokenStream {
-        let param_name = &self.name;
-        let ty = &self.rust_type;
+        if self.optional_out {
+            let record_check = if self.out_mode == Some(OutMode::CallerAllocates) {
+                let inner = self
+                    .mut_ref_inner()
+                    .expect("caller-allocates parameters are mutable references");
+                quote! { ::gobject_ffi::assert_record::<#inner>(); }
+            } else {
+                quote! {}
+            };
+            return quote! {
+                #record_check
+                let #param_name: #ty = if #param_name.is_null() {
+                    None
+                } else {
+                    Some(unsafe { &mut *#param_name })
+                };
+            };
+        }
 
-        if crate::utils::is_mutable_reference(ty) {
+        if let Some(out_mode) = self.out_mode {
+            let param_str = param_name.to_string();
+            let record_check = if out_mode == OutMode::CallerAllocates {
+                let inner = self
+                    .mut_ref_inner()
+                    .expect("caller-allocates parameters are mutable references");
+                quote! { ::gobject_ffi::assert_record::<#inner>(); }
+            } else {
+                quote! {}
+            };
             return quote! {
-                let #param_name: #ty = unsafe { &mut *#param_name };
+                #record_check
+                let #param_name: #ty = match unsafe { #param_name.as_mut() } {
+                    Some(value) => value,
+                    None => {
+                        let err = ::gobject_ffi::strings::null_error(#param_str);
+                        #on_error
+                    }
+                };
+            };
+        }
+
+        if let Some(ref array) = self.array {
+            let len = match self.length_ident() {
+                Some(length) => quote! { #length },
+                None => quote! { 0 },
+            };
+            let values = array.generate_from_c(quote! { #param_name }, len);
+            let array_value = self.array_value_ident();
+            return if array.is_str_refs() {
+                let borrow = if array.nullable {
+                    quote! {
+                        #array_value
+                            .as_ref()
+                            .map(|values| values.iter().map(::std::string::String::as_str).collect::<::std::vec::Vec<_>>())
+                    }
+                } else {
+                    quote! {
+                        #array_value
+                            .iter()
+                            .map(::std::string::String::as_str)
+                            .collect::<::std::vec::Vec<_>>()
+                    }
+                };
+                quote! {
+                    let #array_value = #values;
+                    let #array_value = #borrow;
+                }
+            } else if array.is_slice {
+                quote! {
+                    let #array_value = #values;
+                }
+            } else {
+                quote! {
+                    let #param_name: #ty = #values;
+                }
             };
         }
 
         if let Some(ref override_) = self.c_type_override {
             override_.transfer.convert_from(param_name, ty)
+        } else if self.millis {
+            quote! {
+                let #param_name: #ty = unsafe {
+                    <::gobject_ffi::time::Milliseconds<#ty> as ::gobject_ffi::FfiConvert>::from_c_borrowed(#param_name)
+                }
+                .0;
+            }
+        } else if let Some(ref inner) = self.ref_inner {
+            let ref_value = self.ref_value_ident();
+            let checked =
+                checked_from_c(inner, quote! { try_from_c_borrowed }, param_name, on_error);
+            if crate::utils::extract_option_inner(ty).is_some() {
+                quote! {
+                    let #ref_value: ::std::option::Option<#inner> = if #param_name.is_null() {
+                        None
+                    } else {
+                        Some(#checked)
+                    };
+                }
+            } else {
+                quote! {
+                    let #ref_value: #inner = #checked;
+                }
+            }
+        } else if let Some(ref inner) = self.nullable_inner {
+            quote! {
+                let #param_name: #ty = if #param_name.is_null() {
+                    None
+                } else {
+                    Some(unsafe { <#inner as ::gobject_ffi::FfiConvert>::from_c_borrowed(*#param_name) })
+                };
+            }
         } else {
+            let convert = if self.owned {
+                quote! { try_from_c_owned }
+            } else {
+                quote! { try_from_c_borrowed }
+            };
+            let checked = checked_from_c(ty, convert, param_name, on_error);
             quote! {
-                let #param_name: #ty = unsafe { <#ty as ::gobject_ffi::FfiConvert>::from_c_borrowed(#param_name) };
+                let #param_name: #ty = #checked;
             }
         }
     }
 
-    fn to_header_param(&self) -> String {
-        let c_type = if let Some(ref override_) = self.c_type_override {
+    fn header_c_type(&self) -> String {
+        if let Some(ref closure) = self.closure {
+            closure.typedef_name.clone()
+        } else if let Some(ref override_) = self.c_type_override {
             crate::utils::rust_type_to_c_type_string(&override_.c_type)
+        } else if self.millis {
+            "guint".to_string()
+        } else if self.variant.is_some() {
+            "GVariant*".to_string()
+        } else if self.borrowed_str.is_some() {
+            "const char*".to_string()
+        } else if let Some(ref array) = self.array {
+            if self.out_mode.is_some() {
+                format!("{}*", array.header_c_type())
+            } else {
+                array.header_c_type()
+            }
+        } else if let Some(ref inner) = self.nullable_inner {
+            format!("const {}*", crate::utils::rust_type_to_c_type_string(inner))
+        } else if self.out_mode == Some(OutMode::CallerAllocates) {
+            // The caller passes a pointer to the struct itself
+            let inner = self
+                .mut_ref_inner()
+                .expect("caller-allocates parameters are mutable references");
+            crate::utils::rust_type_to_c_type_string(inner)
         } else {
             crate::utils::rust_type_to_c_type_string(&self.rust_type)
-        };
+        }
+    }
+
+    fn to_header_param(&self) -> String {
+        if let Some(ref closure) = self.closure {
+            return closure.to_header_param(&self.name);
+        }
 
-        format!("{} {}", c_type, self.name)
+        let param = format!("{} {}", self.header_c_type(), self.name);
+        match self.length_ident() {
+            Some(length) if self.out_mode.is_some() => format!("{}, gsize* {}", param, length),
+            Some(length) => format!("{}, gsize {}", param, length),
+            None => param,
+        }
+    }
+
+    /// Whether C may pass `NULL` for this out parameter
+    fn is_optional_out(&self) -> bool {
+        self.optional_out || (self.out_mode == Some(OutMode::Out) && self.c_type_override.is_none())
     }
 
     fn to_gtk_doc_annotation(&self, _c_type_name: &str) -> String {
+        if let Some(ref closure) = self.closure {
+            return closure.to_gtk_doc_annotation(&self.name);
+        }
+
         let mut annotations = Vec::new();
 
-        if crate::utils::extract_option_inner(&self.rust_type).is_some() {
+        let value_type = self.mut_ref_inner().unwrap_or(&self.rust_type);
+        let fd_ownership = crate::utils::fd_ownership(value_type);
+
+        // `None` file descriptors and times are sentinel integers rather
+        // than `NULL`
+        if crate::utils::extract_option_inner(&self.rust_type).is_some()
+            && !self.optional_out
+            && fd_ownership.is_none()
+            && !crate::utils::is_time_type(value_type)
+        {
             annotations.push("nullable".to_string());
         }
 
-        if crate::utils::is_mutable_reference(&self.rust_type) {
-            annotations.push("out".to_string());
+        if let Some(out_mode) = self.out_mode {
+            annotations.push(out_mode.to_gir_annotation().to_string());
+            if self.is_optional_out() {
+                annotations.push("optional".to_string());
+            }
+        }
+
+        let length = self.length_ident();
+        let type_annotations = if self.c_type_override.is_none() {
+            crate::utils::gir_type_annotations(value_type)
+        } else {
+            Vec::new()
+        };
+        // Containers handed to Rust state that their contents stay owned by
+        // the caller
+        let is_container =
+            !type_annotations.is_empty() && !crate::utils::is_filename_type(&self.rust_type);
+        if let Some(ref array) = self.array {
+            let length = length.as_ref().map(ToString::to_string).unwrap_or_default();
+            annotations.extend(array.gir_annotations(&length));
+        } else {
+            annotations.extend(type_annotations);
         }
 
         if let Some(ref override_) = self.c_type_override {
             if let Some(transfer) = override_.transfer.to_gir_annotation() {
                 annotations.push(transfer.to_string());
             }
+        } else if let Some(ref array) = self.array {
+            if let Some(transfer) = array.transfer.to_gir_annotation() {
+                annotations.push(transfer.to_string());
+            }
+        } else if self.out_value_type().is_some_and(|inner| {
+            !crate::utils::is_primitive_type(inner) && !crate::utils::is_time_type(inner)
+        }) || self.owned
+        {
+            annotations.push("transfer full".to_string());
+        } else if is_container || fd_ownership == Some(crate::utils::FdOwnership::Borrowed) {
+            annotations.push("transfer none".to_string());
         }
 
         let annotation_str = if annotations.is_empty() {
@@ -118,9 +1846,117 @@ impl FfiParam {
             format!(" ({})", annotations.join(") ("))
         };
 
-        let c_type_str = crate::utils::rust_type_to_c_type_string(&self.rust_type);
+        let unit = if self.millis {
+            DurationUnit::Millis
+        } else {
+            DurationUnit::Micros
+        };
+        let description = match crate::utils::fd_ownership_doc(value_type, self.out_mode.is_none())
+            .or_else(|| crate::utils::time_unit_doc(value_type, unit))
+            .or_else(|| self.variant.as_ref().map(|variant| variant.doc(value_type)))
+            .or_else(|| {
+                self.in_place
+                    .then(|| "which must stay valid until the operation completes".to_string())
+            }) {
+            Some(detail) => format!("{}, {}", self.header_c_type(), detail),
+            None => self.header_c_type(),
+        };
+        let doc = format!(" * @{}:{} {}", self.name, annotation_str, description);
+        match (length, self.out_mode) {
+            (Some(length), Some(out_mode)) => format!(
+                "{}\n * @{}: ({}) gsize*",
+                doc,
+                length,
+                out_mode.to_gir_annotation()
+            ),
+            (Some(length), None) => format!("{}\n * @{}: gsize", doc, length),
+            (None, _) => doc,
+        }
+    }
+}
+
+/// A return value handed back to C through trailing out parameters
+enum OutReturn {
+    /// `Option<primitive>`: `gboolean` return plus `out_value`
+    Nullable(Type),
+    /// Tuple elements, each written to its own `out_<name>` parameter
+    Tuple {
+        elems: Vec<(syn::Ident, Type)>,
+        /// The first element is the C return value instead of an out parameter
+        return_first: bool,
+    },
+    /// A `ty = "struct"` record copied into `(out caller-allocates)`
+    /// storage passed as `out_result`
+    Record { c_type_name: syn::Ident },
+}
+
+impl OutReturn {
+    fn from_tuple(
+        elems: Vec<Type>,
+        ffi_attrs: &crate::types::FfiAttrs,
+        span: proc_macro2::Span,
+    ) -> syn::Result<Self> {
+        let names = match ffi_attrs.out_names {
+            Some(ref names) => {
+                if names.len() != elems.len() {
+                    return Err(syn::Error::new(
+                        names.first().map_or(span, |name| name.span()),
+                        format!(
+                            "`out_names` has {} names but the tuple has {} elements",
+                            names.len(),
+                            elems.len()
+                        ),
+                    ));
+                }
+                names
+                    .iter()
+                    .map(|name| syn::Ident::new(&format!("out_{}", name), name.span()))
+                    .collect()
+            }
+            None => (0..elems.len())
+                .map(|i| syn::Ident::new(&format!("out_{}", i), span))
+                .collect::<Vec<_>>(),
+        };
+
+        Ok(OutReturn::Tuple {
+            elems: names.into_iter().zip(elems).collect(),
+            return_first: ffi_attrs.return_first,
+        })
+    }
+
+    /// The `(C name, Rust type)` pairs passed as out parameters and
+    /// converted through `FfiConvert`
+    fn out_elems(&self) -> Vec<(syn::Ident, &Type)> {
+        match self {
+            OutReturn::Record { .. } => Vec::new(),
+            OutReturn::Nullable(inner) => vec![(
+                syn::Ident::new("out_value", proc_macro2::Span::call_site()),
+                inner,
+            )],
+            OutReturn::Tuple {
+                elems,
+                return_first,
+            } => elems
+                .iter()
+                .skip(usize::from(*return_first))
+                .map(|(name, ty)| (name.clone(), ty))
+                .collect(),
+        }
+    }
+
+    /// The tuple element returned directly, if any
+    fn returned_elem(&self) -> Option<&Type> {
+        self.returned_elem_named().map(|(_, ty)| ty)
+    }
 
-        format!(" * @{}:{} {}", self.name, annotation_str, c_type_str)
+    fn returned_elem_named(&self) -> Option<(&syn::Ident, &Type)> {
+        match self {
+            OutReturn::Tuple {
+                elems,
+                return_first: true,
+            } => elems.first().map(|(name, ty)| (name, ty)),
+            _ => None,
+        }
     }
 }
 
@@ -129,24 +1965,148 @@ struct FfiReturn {
     c_type: proc_macro2::TokenStream,
     transfer: TransferMode,
     uses_ffi_convert: bool,
+    /// Values written through out parameters instead of being returned
+    out_return: Option<OutReturn>,
+    /// `Vec` returned as a C array, with its length in `out_length`
+    array: Option<CArray>,
+    is_fallible: bool,
+    /// C value returned on failure instead of the type's default, with its
+    /// C spelling for the documentation
+    error_value: Option<(syn::Expr, String)>,
+    /// `Duration` returned as `guint` milliseconds
+    millis: bool,
+    variant: Option<TypedVariant>,
 }
 
 impl FfiReturn {
+    /// `returns_self` is set for constructors and for record methods
+    /// returning `Self`, which both return a new value of the impl type
     fn new(
         rust_type: Type,
         c_return_type_override: Option<crate::types::CTypeOverride>,
         ffi_type: FfiType,
         c_type_name: &syn::Ident,
-        is_constructor: bool,
-    ) -> Self {
+        returns_self: bool,
+        is_fallible: bool,
+        ffi_attrs: &crate::types::FfiAttrs,
+    ) -> syn::Result<Self> {
+        // Records are returned through caller-allocated storage instead
+        let is_record = returns_self && ffi_type == FfiType::Struct;
+
+        let variant = if c_return_type_override.is_some() || returns_self {
+            None
+        } else {
+            TypedVariant::new(&rust_type, ffi_attrs, &rust_type)?
+        };
+
+        let out_return = if is_record && c_return_type_override.is_none() {
+            Some(OutReturn::Record {
+                c_type_name: c_type_name.clone(),
+            })
+        } else if c_return_type_override.is_some() || returns_self || variant.is_some() {
+            None
+        } else if let Some(elems) = crate::utils::extract_tuple_elems(&rust_type) {
+            Some(OutReturn::from_tuple(
+                elems,
+                ffi_attrs,
+                proc_macro2::Span::call_site(),
+            )?)
+        } else if ffi_attrs.sentinel {
+            None
+        } else {
+            crate::utils::extract_nullable_primitive(&rust_type)
+                .map(|inner| OutReturn::Nullable(inner.clone()))
+        };
+
+        if !matches!(out_return, Some(OutReturn::Tuple { .. })) {
+            if let Some(name) = ffi_attrs.out_names.as_ref().and_then(|names| names.first()) {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "`out_names` requires a method returning a tuple",
+                ));
+            }
+            if ffi_attrs.return_first {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "`return_first` requires a method returning a tuple",
+                ));
+            }
+        }
+
+        // Byte buffers are handed out as a `GBytes` owning the `Vec` instead
+        // of a copied `guint8*` array, unless array options ask for one
+        let byte_vec = crate::utils::is_byte_vec(&rust_type)
+            && ffi_attrs.transfer.is_none()
+            && ffi_attrs.zero_terminated.is_none()
+            && ffi_attrs.collection.is_none();
+        let array = if c_return_type_override.is_some()
+            || returns_self
+            || out_return.is_some()
+            || variant.is_some()
+            || byte_vec
+        {
+            None
+        } else {
+            CArray::from_type(&rust_type, ffi_attrs, TransferMode::Full)
+        };
+
+        match array {
+            Some(CArray { is_slice: true, .. }) => {
+                return Err(syn::Error::new_spanned(
+                    &rust_type,
+                    "slices cannot be returned to C, return a `Vec` instead",
+                ));
+            }
+            Some(ref array) if array.is_str_refs() => {
+                return Err(syn::Error::new_spanned(
+                    &rust_type,
+                    "borrowed strings cannot be returned to C, return a `Vec<String>` instead",
+                ));
+            }
+            None if ffi_attrs.transfer.is_some()
+                || ffi_attrs.zero_terminated.is_some()
+                || ffi_attrs.collection.is_some() =>
+            {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "`transfer`, `zero_terminated` and `collection` require a method returning a `Vec`",
+                ));
+            }
+            _ => {}
+        }
+
+        let millis = duration_millis(&rust_type, ffi_attrs, &rust_type)?;
+
         let (c_type, transfer, uses_ffi_convert) = if let Some(ref override_) =
             c_return_type_override
         {
             let c = &override_.c_type;
             (quote! { #c }, override_.transfer, false)
+        } else if millis {
+            let c_type = quote! {
+                <::gobject_ffi::time::Milliseconds<#rust_type> as ::gobject_ffi::FfiConvert>::CType
+            };
+            (c_type, TransferMode::Primitive, true)
+        } else if variant.is_some() {
+            (
+                quote! { *mut ::glib::ffi::GVariant },
+                TransferMode::Full,
+                false,
+            )
         } else if crate::utils::is_unit_type(&rust_type) {
             (quote! { () }, TransferMode::None, false)
-        } else if is_constructor
+        } else if let Some(ref out_return) = out_return {
+            let c_type = if let Some(first) = out_return.returned_elem() {
+                quote! { <#first as ::gobject_ffi::FfiConvert>::CType }
+            } else if is_fallible || matches!(out_return, OutReturn::Nullable(_)) {
+                quote! { ::glib::ffi::gboolean }
+            } else {
+                quote! { () }
+            };
+            (c_type, TransferMode::Full, true)
+        } else if let Some(ref array) = array {
+            (array.c_type(), array.transfer, false)
+        } else if returns_self
             && (ffi_type.is_gobject() || matches!(ffi_type, FfiType::Boxed | FfiType::Shared))
         {
             let c_type = if ffi_type.is_gobject() {
@@ -165,24 +2125,222 @@ impl FfiReturn {
             (c_type, TransferMode::Full, true)
         };
 
-        Self {
+        Ok(Self {
             rust_type,
             c_type,
             transfer,
             uses_ffi_convert,
-        }
+            out_return,
+            array,
+            is_fallible,
+            error_value: ffi_attrs
+                .error_value
+                .as_ref()
+                .map(|error_value| {
+                    crate::utils::c_value_string(error_value)
+                        .map(|c_value| (error_value.clone(), c_value))
+                })
+                .transpose()?,
+            millis,
+            variant,
+        })
     }
 
     fn is_void(&self) -> bool {
         crate::utils::is_unit_type(&self.rust_type)
     }
 
-    fn generate_ok_handler(&self) -> proc_macro2::TokenStream {
+    /// Whether the C function returns `void` despite producing a value
+    fn returns_only_out_params(&self) -> bool {
+        matches!(
+            self.out_return,
+            Some(
+                OutReturn::Tuple {
+                    return_first: false,
+                    ..
+                } | OutReturn::Record { .. }
+            )
+        ) && !self.is_fallible
+    }
+
+    /// Whether the async result has no `GValue` type and travels through the
+    /// task boxed
+    fn boxes_task_value(&self) -> bool {
+        let rust_type =
+            crate::utils::extract_option_inner(&self.rust_type).unwrap_or(&self.rust_type);
+        self.out_return.is_some()
+            || self.array.is_some()
+            || self.variant.is_some()
+            || crate::utils::extract_map_types(rust_type).is_some()
+            || crate::utils::is_byte_vec(rust_type)
+            || matches!(
+                crate::utils::last_segment_ident(rust_type).as_deref(),
+                Some("Duration" | "SystemTime" | "Value")
+            )
+    }
+
+    /// Whether the async result may not be `Send`, as `gio::Task` requires:
+    /// boxed values, and objects, which are only `Send` when their subclass
+    /// is
+    fn needs_local_task(&self) -> bool {
+        let rust_type =
+            crate::utils::extract_option_inner(&self.rust_type).unwrap_or(&self.rust_type);
+        self.boxes_task_value()
+            || !(self.is_void()
+                || crate::utils::is_primitive_type(rust_type)
+                || crate::utils::is_string_type(rust_type)
+                || crate::utils::is_filename_type(rust_type))
+    }
+
+    /// The out parameter receiving the length of a returned array
+    fn length_ident(&self) -> Option<syn::Ident> {
+        match self.array {
+            Some(ref array) if array.has_length() => Some(syn::Ident::new(
+                "out_length",
+                proc_macro2::Span::call_site(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn out_elems(&self) -> Vec<(syn::Ident, &Type)> {
+        self.out_return
+            .as_ref()
+            .map(OutReturn::out_elems)
+            .unwrap_or_default()
+    }
+
+    /// The `(out caller-allocates)` parameter receiving a returned record
+    fn record_ident(&self) -> Option<syn::Ident> {
+        match self.out_return {
+            Some(OutReturn::Record { .. }) => Some(syn::Ident::new(
+                "out_result",
+                proc_macro2::Span::call_site(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Extra C parameters used to hand the result back, placed before
+    /// `GError **error`
+    fn generate_out_params(&self) -> proc_macro2::TokenStream {
+        let params = self.out_elems().into_iter().map(|(name, ty)| {
+            quote! { #name: *mut <#ty as ::gobject_ffi::FfiConvert>::CType, }
+        });
+        let record = self.record_ident().map(|record| {
+            let rust_type = &self.rust_type;
+            quote! { #record: *mut #rust_type, }
+        });
+        let length = self
+            .length_ident()
+            .map(|length| quote! { #length: *mut usize, });
+        quote! { #(#params)* #record #length }
+    }
+
+    fn generate_out_args(&self) -> proc_macro2::TokenStream {
+        let names = self
+            .out_elems()
+            .into_iter()
+            .map(|(name, _)| name)
+            .chain(self.record_ident())
+            .chain(self.length_ident());
+        quote! { #(#names,)* }
+    }
+
+    /// Converts the returned `val` for C, keeping arrays C doesn't own under
+    /// the C function name `fn_name`
+    fn generate_ok_handler(&self, fn_name: &str) -> proc_macro2::TokenStream {
         if self.is_void() {
             return quote! { () };
         }
 
-        if self.uses_ffi_convert {
+        match self.out_return {
+            Some(OutReturn::Nullable(ref inner)) => {
+                return quote! {
+                    match val {
+                        Some(val) => {
+                            if !out_value.is_null() {
+                                unsafe {
+                                    *out_value = <#inner as ::gobject_ffi::FfiConvert>::to_c_owned(val);
+                                }
+                            }
+                            ::glib::ffi::GTRUE
+                        }
+                        None => ::glib::ffi::GFALSE,
+                    }
+                };
+            }
+            Some(OutReturn::Tuple {
+                ref elems,
+                return_first,
+            }) => {
+                let bindings = (0..elems.len())
+                    .map(|i| quote::format_ident!("val_{}", i))
+                    .collect::<Vec<_>>();
+                let writes = elems
+                    .iter()
+                    .zip(&bindings)
+                    .skip(usize::from(return_first))
+                    .map(|((name, ty), binding)| {
+                        quote! {
+                            if !#name.is_null() {
+                                unsafe {
+                                    *#name = <#ty as ::gobject_ffi::FfiConvert>::to_c_owned(#binding);
+                                }
+                            }
+                        }
+                    });
+                let ret = if return_first {
+                    let (_, first) = &elems[0];
+                    let binding = &bindings[0];
+                    quote! { <#first as ::gobject_ffi::FfiConvert>::to_c_owned(#binding) }
+                } else if self.is_fallible {
+                    quote! { ::glib::ffi::GTRUE }
+                } else {
+                    quote! {}
+                };
+                return quote! {
+                    {
+                        let (#(#bindings,)*) = val;
+                        #(#writes)*
+                        #ret
+                    }
+                };
+            }
+            Some(OutReturn::Record { .. }) => {
+                let ret = if self.is_fallible {
+                    quote! { ::glib::ffi::GTRUE }
+                } else {
+                    quote! {}
+                };
+                return quote! {
+                    {
+                        if !out_result.is_null() {
+                            unsafe { out_result.write(val) };
+                        }
+                        #ret
+                    }
+                };
+            }
+            None => {}
+        }
+
+        if let Some(ref array) = self.array {
+            return array.generate_to_c(quote! { val }, self.length_ident().as_ref(), fn_name);
+        }
+
+        if let Some(ref variant) = self.variant {
+            return variant.generate_to_c(&self.rust_type);
+        }
+
+        if self.millis {
+            let rust_type = &self.rust_type;
+            quote! {
+                <::gobject_ffi::time::Milliseconds<#rust_type> as ::gobject_ffi::FfiConvert>::to_c_owned(
+                    ::gobject_ffi::time::Milliseconds(val),
+                )
+            }
+        } else if self.uses_ffi_convert {
             let rust_type = &self.rust_type;
             quote! { <#rust_type as ::gobject_ffi::FfiConvert>::to_c_owned(val) }
         } else {
@@ -191,11 +2349,31 @@ impl FfiReturn {
     }
 
     fn generate_err_handler(&self) -> proc_macro2::TokenStream {
-        if self.is_void() {
+        if self.is_void() || self.returns_only_out_params() {
             return quote! { () };
         }
 
-        if self.uses_ffi_convert {
+        if let Some((ref error_value, _)) = self.error_value {
+            return quote! { #error_value };
+        }
+
+        if let Some(ref out_return) = self.out_return {
+            return match out_return.returned_elem() {
+                Some(first) => quote! { <#first as ::gobject_ffi::FfiConvert>::c_error_value() },
+                None => quote! { ::glib::ffi::GFALSE },
+            };
+        }
+
+        if self.array.is_some() || self.variant.is_some() {
+            return quote! { ::std::ptr::null_mut() };
+        }
+
+        if self.millis {
+            let rust_type = &self.rust_type;
+            quote! {
+                <::gobject_ffi::time::Milliseconds<#rust_type> as ::gobject_ffi::FfiConvert>::c_error_value()
+            }
+        } else if self.uses_ffi_convert {
             let rust_type = &self.rust_type;
             quote! { <#rust_type as ::gobject_ffi::FfiConvert>::c_error_value() }
         } else {
@@ -205,41 +2383,216 @@ impl FfiReturn {
 
     /// Generate C header return type string
     fn to_header_type(&self) -> String {
-        if self.is_void() {
+        if self.is_void() || self.returns_only_out_params() {
             return "void".to_string();
         }
 
+        if let Some(ref out_return) = self.out_return {
+            return match out_return.returned_elem() {
+                Some(first) => crate::utils::rust_type_to_c_type_string(first),
+                None => "gboolean".to_string(),
+            };
+        }
+
+        if let Some(ref array) = self.array {
+            return array.header_c_type();
+        }
+
+        if self.millis {
+            return "guint".to_string();
+        }
+
+        if self.variant.is_some() {
+            return "GVariant*".to_string();
+        }
+
         crate::utils::rust_type_to_c_type_string(&self.rust_type)
     }
 
+    /// Generate C header out parameters
+    fn to_header_out_params(&self) -> Vec<String> {
+        self.out_elems()
+            .into_iter()
+            .map(|(name, ty)| format!("{}* {}", crate::utils::rust_type_to_c_type_string(ty), name))
+            .chain(match self.out_return {
+                Some(OutReturn::Record {
+                    ref c_type_name, ..
+                }) => Some(format!("{}* out_result", c_type_name)),
+                _ => None,
+            })
+            .chain(
+                self.length_ident()
+                    .map(|length| format!("gsize* {}", length)),
+            )
+            .collect()
+    }
+
+    /// Generate GtkDoc annotations for the out parameters
+    fn to_gtk_doc_out_params(&self) -> Vec<String> {
+        self.out_elems()
+            .into_iter()
+            .map(|(name, ty)| {
+                let transfer = if crate::utils::fd_ownership(ty)
+                    == Some(crate::utils::FdOwnership::Borrowed)
+                {
+                    " (transfer none)"
+                } else if crate::utils::is_primitive_type(ty) || crate::utils::is_time_type(ty) {
+                    ""
+                } else {
+                    " (transfer full)"
+                };
+                let type_annotations = crate::utils::gir_type_annotations(ty)
+                    .iter()
+                    .map(|annotation| format!(" ({})", annotation))
+                    .collect::<String>();
+                let what = name.to_string();
+                let what = what.strip_prefix("out_").unwrap_or(&what);
+                let what = match crate::utils::fd_ownership_doc(ty, false)
+                    .or_else(|| crate::utils::time_unit_doc(ty, DurationUnit::Micros))
+                {
+                    Some(detail) => format!("{}, {}", what, detail),
+                    None => what.to_string(),
+                };
+                format!(
+                    " * @{}: (out) (optional){}{}: return location for the {}",
+                    name, type_annotations, transfer, what
+                )
+            })
+            .chain(self.record_ident().map(|record| {
+                format!(
+                    " * @{}: (out caller-allocates): return location for the result",
+                    record
+                )
+            }))
+            .chain(self.length_ident().map(|length| {
+                format!(
+                    " * @{}: (out) (optional): return location for the length of the result",
+                    length
+                )
+            }))
+            .collect()
+    }
+
     /// Generate GtkDoc Returns annotation
     fn to_gtk_doc_returns(&self) -> Option<String> {
-        if self.is_void() {
+        if self.is_void() || self.returns_only_out_params() {
             return None;
         }
 
+        let (returned_type, what) = match self.out_return {
+            Some(OutReturn::Nullable(_)) => {
+                return Some(" * Returns: %TRUE if @out_value was set".to_string());
+            }
+            Some(ref out_return) => match out_return.returned_elem_named() {
+                Some((name, first)) => {
+                    let name = name.to_string();
+                    let name = name.strip_prefix("out_").unwrap_or(&name).to_string();
+                    (first, name)
+                }
+                None => {
+                    return Some(
+                        " * Returns: %TRUE on success, %FALSE if @error is set".to_string(),
+                    );
+                }
+            },
+            None => (&self.rust_type, "result".to_string()),
+        };
+
         let mut annotations = Vec::new();
 
-        if !crate::utils::is_primitive_type(&self.rust_type) {
+        if let Some(ref array) = self.array {
+            let length = self
+                .length_ident()
+                .map(|length| length.to_string())
+                .unwrap_or_default();
+            annotations.extend(array.gir_annotations(&length));
+        } else if self.variant.is_none() {
+            annotations.extend(crate::utils::gir_type_annotations(returned_type));
+        }
+
+        let fd_ownership = crate::utils::fd_ownership(returned_type);
+        if self.variant.is_some() {
+            annotations.push("transfer full".to_string());
+        } else if fd_ownership == Some(crate::utils::FdOwnership::Borrowed) {
+            annotations.push("transfer none".to_string());
+        } else if !crate::utils::is_primitive_type(returned_type)
+            && !crate::utils::is_time_type(returned_type)
+        {
             if let Some(transfer) = self.transfer.to_gir_annotation() {
                 annotations.push(transfer.to_string());
             }
         }
 
-        if crate::utils::extract_option_inner(&self.rust_type).is_some() {
+        // `None` file descriptors and times are sentinel integers rather
+        // than `NULL`
+        if crate::utils::extract_option_inner(returned_type).is_some()
+            && fd_ownership.is_none()
+            && !crate::utils::is_time_type(returned_type)
+        {
             annotations.push("nullable".to_string());
         }
 
+        let unit = if self.millis {
+            DurationUnit::Millis
+        } else {
+            DurationUnit::Micros
+        };
+        let what = match crate::utils::fd_ownership_doc(returned_type, false)
+            .or_else(|| crate::utils::time_unit_doc(returned_type, unit))
+            .or_else(|| {
+                self.variant
+                    .as_ref()
+                    .map(|variant| variant.doc(returned_type))
+            }) {
+            Some(detail) => format!("{}, {}", what, detail),
+            None => what,
+        };
+        let what = match self.array.as_ref().map(|array| array.transfer) {
+            Some(TransferMode::None) => {
+                format!("{}, owned by @self until the next call", what)
+            }
+            Some(TransferMode::Container) => format!(
+                "{}, whose elements are owned by @self until the next call",
+                what
+            ),
+            _ => what,
+        };
+
         let annotation_str = if annotations.is_empty() {
             String::new()
         } else {
             format!(" ({})", annotations.join(") ("))
         };
 
-        Some(format!(" * Returns:{} the result", annotation_str))
+        match self.error_value {
+            Some((_, ref error_value)) => {
+                // gtk-doc links constants such as %G_MININT32
+                let error_value = match error_value.strip_prefix('-') {
+                    Some(value) if value.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                        format!("-%{}", value)
+                    }
+                    _ if error_value.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                        format!("%{}", error_value)
+                    }
+                    _ => error_value.clone(),
+                };
+                Some(format!(
+                    " * Returns:{} the {}, or {} on error",
+                    annotation_str, what, error_value
+                ))
+            }
+            None => Some(format!(" * Returns:{} the {}", annotation_str, what)),
+        }
     }
 }
 
+/// Accessor of a property declared in `#[ffi_impl(properties(...))]`, which
+/// goes through `gobject_ffi::property` instead of calling a Rust method
+enum PropertyAccess {
+    Get(String),
+    Set(String),
+}
+
 pub(crate) struct FfiMethod {
     rust_name: syn::Ident,
     self_type: Option<Type>,
@@ -251,9 +2604,70 @@ pub(crate) struct FfiMethod {
     is_fallible: bool,
     async_names: Option<AsyncFunctionNames>,
     ffi_type: FfiType,
+    /// Name of the signal this method emits, which gets a `connect_` helper
+    signal: Option<String>,
+    property: Option<PropertyAccess>,
+    /// The exported function calls the class struct slot, which defaults to
+    /// the Rust method
+    vfunc: bool,
+    /// `&mut self`, which takes a non-`const` pointer to a record
+    mut_self: bool,
 }
 
 impl FfiMethod {
+    /// `get_` and `set_` exports for a declared property, following its
+    /// `get`, `set` and `construct_only` flags
+    pub(crate) fn from_property(
+        property: &crate::types::FfiProperty,
+        prefix: &str,
+        impl_self_type: &Type,
+        c_type_name: &syn::Ident,
+        ffi_type: FfiType,
+    ) -> syn::Result<Vec<Self>> {
+        if !ffi_type.is_gobject() {
+            return Err(syn::Error::new_spanned(
+                &property.ident,
+                "properties are only supported on GObject types",
+            ));
+        }
+
+        let ty = &property.ty;
+        let mut accessors = Vec::new();
+        if property.readable {
+            let getter = quote::format_ident!("get_{}", property.ident);
+            let method: syn::ImplItemFn = syn::parse_quote! {
+                fn #getter(&self) -> #ty { unreachable!() }
+            };
+            let mut accessor = Self::from_method(
+                &method,
+                prefix,
+                impl_self_type,
+                c_type_name,
+                ffi_type,
+                false,
+            )?;
+            accessor.property = Some(PropertyAccess::Get(property.name.clone()));
+            accessors.push(accessor);
+        }
+        if property.writable && !property.construct_only {
+            let setter = quote::format_ident!("set_{}", property.ident);
+            let method: syn::ImplItemFn = syn::parse_quote! {
+                fn #setter(&self, value: #ty) { unreachable!() }
+            };
+            let mut accessor = Self::from_method(
+                &method,
+                prefix,
+                impl_self_type,
+                c_type_name,
+                ffi_type,
+                false,
+            )?;
+            accessor.property = Some(PropertyAccess::Set(property.name.clone()));
+            accessors.push(accessor);
+        }
+        Ok(accessors)
+    }
+
     pub(crate) fn from_method(
         method: &syn::ImplItemFn,
         prefix: &str,
@@ -275,11 +2689,46 @@ impl FfiMethod {
             None
         };
 
-        let params = FfiParam::extract_from_method(method, !is_constructor)?;
+        let params = FfiParam::extract_from_method(method, !is_constructor, c_type_name)?;
 
         let c_return_type = crate::utils::extract_c_return_type(&method.attrs)?;
+        let ffi_attrs = crate::utils::extract_ffi_attrs(&method.attrs)?;
+
+        if let Some(ref error_value) = ffi_attrs.error_value {
+            if !is_fallible && !is_async {
+                return Err(syn::Error::new_spanned(
+                    error_value,
+                    "`error_value` only applies to methods returning `Result` or async methods",
+                ));
+            }
+        }
+
+        if ffi_type == FfiType::Struct && is_async {
+            return Err(syn::Error::new_spanned(
+                &method.sig,
+                "async methods are not supported on `struct` types, which are only borrowed for the duration of the call",
+            ));
+        }
+
+        // Records are returned the same way from constructors and from
+        // methods returning `Self`
+        let is_record = ffi_type == FfiType::Struct
+            && (is_constructor
+                || match &method.sig.output {
+                    ReturnType::Default => false,
+                    ReturnType::Type(_, ty) => {
+                        let ty = if is_fallible {
+                            crate::utils::extract_result_ok_type_as_type(ty)
+                        } else {
+                            (**ty).clone()
+                        };
+                        let name = crate::utils::last_segment_ident(&ty);
+                        name.as_deref() == Some("Self")
+                            || name == crate::utils::last_segment_ident(impl_self_type)
+                    }
+                });
 
-        let rust_return_type = if is_constructor {
+        let rust_return_type = if is_constructor || is_record {
             syn::parse_quote! { super::#impl_self_type }
         } else {
             match &method.sig.output {
@@ -299,8 +2748,50 @@ impl FfiMethod {
             c_return_type,
             ffi_type,
             c_type_name,
-            is_constructor,
-        );
+            is_constructor || is_record,
+            is_fallible,
+            &ffi_attrs,
+        )?;
+
+        if let Some(ref error_value) = ffi_attrs.error_value {
+            if return_info.is_void() {
+                return Err(syn::Error::new_spanned(
+                    error_value,
+                    "`error_value` requires a method with a return value",
+                ));
+            }
+        }
+
+        let signal = match ffi_attrs.signal {
+            Some(ref name) => {
+                Self::check_signal(&method.sig, ffi_type, &params, &return_info)?;
+                Some(match name {
+                    Some(name) => name.value(),
+                    None => fn_name.to_string().replace('_', "-"),
+                })
+            }
+            None => None,
+        };
+
+        // Arrays C doesn't own are kept alive on the object
+        if return_info
+            .array
+            .as_ref()
+            .is_some_and(|array| array.transfer != TransferMode::Full)
+            && (!ffi_type.is_gobject() || is_constructor || is_async)
+        {
+            return Err(syn::Error::new_spanned(
+                &method.sig,
+                "returned `transfer none` and `transfer container` arrays are kept on the object, which requires a synchronous method on a GObject type",
+            ));
+        }
+
+        if ffi_attrs.vfunc && (!ffi_type.is_gobject() || is_constructor || is_async) {
+            return Err(syn::Error::new_spanned(
+                &method.sig,
+                "`vfunc` requires a synchronous method on a GObject type",
+            ));
+        }
 
         let method_self_type = if is_constructor {
             None
@@ -319,33 +2810,129 @@ impl FfiMethod {
             is_fallible,
             async_names,
             ffi_type,
+            signal,
+            property: None,
+            vfunc: ffi_attrs.vfunc,
+            mut_self: method.sig.receiver().is_some_and(|receiver| {
+                receiver.reference.is_some() && receiver.mutability.is_some()
+            }),
         })
     }
 
+    /// Signal handlers are called with the same values as the emitting
+    /// method, which must fit in a single C argument each
+    fn check_signal(
+        sig: &syn::Signature,
+        ffi_type: FfiType,
+        params: &[FfiParam],
+        return_info: &FfiReturn,
+    ) -> syn::Result<()> {
+        let has_self = sig
+            .inputs
+            .iter()
+            .any(|arg| matches!(arg, syn::FnArg::Receiver(_)));
+        if !ffi_type.is_gobject() || !has_self || sig.asyncness.is_some() || return_info.is_fallible
+        {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "`signal` requires a synchronous, infallible method on a GObject type",
+            ));
+        }
+        for param in params {
+            if param.out_mode.is_some()
+                || param.array.is_some()
+                || param.closure.is_some()
+                || param.nullable_inner.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    &param.rust_type,
+                    "signal parameters are passed as a single C value",
+                ));
+            }
+        }
+        if return_info.out_return.is_some() || return_info.array.is_some() {
+            return Err(syn::Error::new_spanned(
+                &sig.output,
+                "signals return a single C value",
+            ));
+        }
+        Ok(())
+    }
+
     fn is_constructor(&self) -> bool {
         self.self_type.is_none()
     }
 
-    fn param_names(&self) -> Vec<&syn::Ident> {
-        self.params.iter().map(|p| &p.name).collect()
+    fn param_names(&self) -> Vec<syn::Ident> {
+        self.params
+            .iter()
+            .flat_map(FfiParam::c_param_names)
+            .collect()
     }
 
     fn generate_ffi_params_inner(&self) -> proc_macro2::TokenStream {
-        let params = self.params.iter().map(|p| {
-            let name = &p.name;
-            let c_type = &p.c_type;
-            quote! { #name: #c_type, }
-        });
+        let params = self.params.iter().map(FfiParam::generate_c_params);
         quote! { #(#params)* }
     }
 
     fn generate_conversions_inner(&self) -> Vec<proc_macro2::TokenStream> {
+        let on_error = self.generate_param_error_handler();
         self.params
             .iter()
-            .map(|p| p.generate_conversion())
+            .map(|p| p.generate_conversion(&on_error))
             .collect()
     }
 
+    /// Rejects a parameter that failed validation, such as a borrowed string
+    /// that is not UTF-8. Async methods complete with `err` so the callback
+    /// still runs, synchronous fallible methods report it through the
+    /// `GError`, all others log it as a critical and return early like
+    /// `g_return_val_if_fail()`
+    fn generate_param_error_handler(&self) -> proc_macro2::TokenStream {
+        let fn_name = &self.ffi_prefix;
+        if let Some(ref async_names) = self.async_names {
+            let async_fn_name = &async_names.async_name;
+            let source_object = if self.ffi_type.is_gobject() && !self.is_constructor() {
+                quote! {
+                    ::glib::translate::ToGlibPtr::to_glib_none(
+                        ::glib::object::Cast::upcast_ref::<::glib::Object>(&self_param),
+                    )
+                    .0
+                }
+            } else {
+                quote! { ::std::ptr::null_mut() }
+            };
+            quote! {{
+                unsafe {
+                    ::gio::ffi::g_task_report_error(
+                        #source_object,
+                        callback,
+                        user_data,
+                        #async_fn_name as *const () as ::glib::ffi::gpointer,
+                        ::glib::translate::IntoGlibPtr::into_glib_ptr(err),
+                    );
+                }
+                return;
+            }}
+        } else if self.is_fallible {
+            let err_handler = self.return_info.generate_err_handler();
+            quote! {{
+                if !error.is_null() {
+                    unsafe {
+                        *error = ::glib::translate::IntoGlibPtr::into_glib_ptr(err);
+                    }
+                }
+                return #err_handler;
+            }}
+        } else {
+            let err_handler = self.return_info.generate_err_handler();
+            quote! {{
+                ::gobject_ffi::strings::critical(#fn_name, &err);
+                return #err_handler;
+            }}
+        }
+    }
+
     fn generate_sync_call_args(&self) -> proc_macro2::TokenStream {
         let param_args = if self.params.is_empty() {
             quote! {}
@@ -363,18 +2950,62 @@ impl FfiMethod {
     }
 
     fn generate_param_call_args(&self) -> proc_macro2::TokenStream {
-        if self.params.is_empty() {
-            quote! {}
-        } else {
-            let param_names = self.param_names();
-            quote! { #(#param_names),* }
-        }
+        let call_args = self.params.iter().map(|p| p.generate_call_arg());
+        quote! { #(#call_args),* }
+    }
+
+    fn generate_write_backs(&self) -> Vec<proc_macro2::TokenStream> {
+        self.params
+            .iter()
+            .filter_map(|p| p.generate_write_back())
+            .collect()
     }
 
     fn generate_body(&self) -> proc_macro2::TokenStream {
         let fn_name = &self.rust_name;
         let param_call_args = self.generate_param_call_args();
 
+        if let Some(ref property) = self.property {
+            let self_type = self
+                .self_type
+                .as_ref()
+                .expect("property accessors are methods");
+            let object = quote! {
+                ::glib::object::Cast::upcast_ref::<::glib::Object>(&self_param)
+            };
+            // The accessors generated by `#[derive(glib::Properties)]` only
+            // exist when the declaration matches the real property
+            return match property {
+                PropertyAccess::Get(name) => {
+                    let return_type = &self.return_info.rust_type;
+                    let err_handler = self.return_info.generate_err_handler();
+                    let getter = syn::Ident::new(&name.replace('-', "_"), fn_name.span());
+                    let check = quote::quote_spanned! {fn_name.span()=>
+                        let _: fn(&#self_type) -> #return_type = <#self_type>::#getter;
+                    };
+                    quote! {{
+                        #check
+                        match ::gobject_ffi::property::get::<#return_type>(#object, #name) {
+                            Some(value) => value,
+                            None => return #err_handler,
+                        }
+                    }}
+                }
+                PropertyAccess::Set(name) => {
+                    let setter =
+                        syn::Ident::new(&format!("set_{}", name.replace('-', "_")), fn_name.span());
+                    let value_type = &self.params[0].rust_type;
+                    let check = quote::quote_spanned! {fn_name.span()=>
+                        let _ = |object: &#self_type, value: #value_type| object.#setter(value);
+                    };
+                    quote! {{
+                        #check
+                        ::gobject_ffi::property::set(#object, #name, #param_call_args)
+                    }}
+                }
+            };
+        }
+
         if self.is_constructor() {
             let return_type = &self.return_info.rust_type;
             quote! { #return_type::#fn_name(#param_call_args) }
@@ -390,7 +3021,11 @@ impl FfiMethod {
             Some(quote! { #c_type })
         } else {
             let c_type_name = &self.c_type_name;
-            if self.ffi_type.is_gobject() {
+            if self.ffi_type == FfiType::Struct && self.mut_self {
+                Some(quote! { *mut #c_type_name })
+            } else if self.ffi_type == FfiType::Struct {
+                Some(quote! { *const #c_type_name })
+            } else if self.ffi_type.is_gobject() {
                 Some(quote! { *mut #c_type_name })
             } else {
                 Some(quote! { #c_type_name })
@@ -402,6 +3037,14 @@ impl FfiMethod {
         let self_type = self.self_type.as_ref()?;
 
         let self_param_ident = syn::Ident::new("self_param", proc_macro2::Span::call_site());
+        // Records are used in place rather than copied
+        if self.ffi_type == FfiType::Struct {
+            return Some(if self.mut_self {
+                quote! { let #self_param_ident: &mut #self_type = unsafe { &mut *#self_param_ident }; }
+            } else {
+                quote! { let #self_param_ident: &#self_type = unsafe { &*#self_param_ident }; }
+            });
+        }
         let transfer = if self.ffi_type.self_c_type().is_some() {
             self.ffi_type.self_transfer_mode()
         } else {
@@ -448,8 +3091,10 @@ impl FfiMethod {
         let conversions = self.generate_conversions();
         let body = self.generate_body();
         let return_type = &self.return_info.c_type;
-        let ok_handler = self.return_info.generate_ok_handler();
+        let ok_handler = self.return_info.generate_ok_handler(&self.ffi_prefix);
         let err_handler = self.return_info.generate_err_handler();
+        let out_params = self.return_info.generate_out_params();
+        let write_backs = self.generate_write_backs();
 
         let error_param = if self.is_fallible {
             quote! { error: *mut *mut ::glib::ffi::GError, }
@@ -460,7 +3105,10 @@ impl FfiMethod {
         let function_body = if self.is_fallible {
             quote! {
                 match #body {
-                    Ok(val) => #ok_handler,
+                    Ok(val) => {
+                        #(#write_backs)*
+                        #ok_handler
+                    }
                     Err(e) => {
                         if !error.is_null() {
                             unsafe {
@@ -474,25 +3122,172 @@ impl FfiMethod {
         } else if self.return_info.is_void() {
             quote! {
                 #body;
+                #(#write_backs)*
             }
         } else {
             quote! {
                 let val = #body;
+                #(#write_backs)*
                 #ok_handler
             }
         };
 
+        let callback_types = self.generate_callback_types();
+        let signal = self.generate_signal();
+
+        if self.vfunc {
+            let real_name = self.vfunc_real_name();
+            let vtable_name = self.vtable_name();
+            let slot = &self.rust_name;
+            let call_args = self.generate_sync_call_args();
+            let out_args = self.return_info.generate_out_args();
+            let error_arg = if self.is_fallible {
+                quote! { error, }
+            } else {
+                quote! {}
+            };
+            let vtable = if self.ffi_type == FfiType::Interface {
+                let self_type = &self.self_type;
+                quote! {
+                    let vtable = unsafe {
+                        let g_class = (*(self_param as *mut ::glib::gobject_ffi::GTypeInstance)).g_class;
+                        let iface_type = ::glib::translate::IntoGlib::into_glib(
+                            <#self_type as ::glib::prelude::StaticType>::static_type(),
+                        );
+                        (::glib::gobject_ffi::g_type_interface_peek(g_class as ::glib::ffi::gpointer, iface_type)
+                            as *const #vtable_name)
+                            .as_ref()
+                    };
+                }
+            } else {
+                quote! {
+                    let vtable = unsafe {
+                        ((*(self_param as *mut ::glib::gobject_ffi::GTypeInstance)).g_class
+                            as *const #vtable_name)
+                            .as_ref()
+                    };
+                }
+            };
+
+            return quote! {
+                #(#callback_types)*
+
+                /// Default implementation of the class struct slot
+                pub unsafe extern "C" fn #real_name(
+                    #params
+                    #out_params
+                    #error_param
+                ) -> #return_type {
+                    #(#conversions)*
+
+                    #function_body
+                }
+
+                #[unsafe(no_mangle)]
+                pub unsafe extern "C" fn #fn_name(
+                    #params
+                    #out_params
+                    #error_param
+                ) -> #return_type {
+                    #vtable
+                    let vfunc = vtable.and_then(|vtable| vtable.#slot).unwrap_or(#real_name);
+                    unsafe { vfunc(#call_args #out_args #error_arg) }
+                }
+
+                #signal
+            };
+        }
+
         quote! {
+            #(#callback_types)*
+
             #[unsafe(no_mangle)]
             pub unsafe extern "C" fn #fn_name(
                 #params
+                #out_params
                 #error_param
             ) -> #return_type {
                 #(#conversions)*
 
                 #function_body
             }
+
+            #signal
+        }
+    }
+
+    /// Class or interface struct holding the virtual method slots
+    fn vtable_name(&self) -> syn::Ident {
+        if self.ffi_type == FfiType::Interface {
+            quote::format_ident!("{}Interface", self.c_type_name)
+        } else {
+            quote::format_ident!("{}Class", self.c_type_name)
+        }
+    }
+
+    /// Rust function filling the class struct slot, such as
+    /// `calculator_real_compute`
+    fn vfunc_real_name(&self) -> syn::Ident {
+        let prefix = self
+            .ffi_prefix
+            .strip_suffix(&self.rust_name.to_string())
+            .expect("the prefix ends with the method name");
+        quote::format_ident!("{}real_{}", prefix, self.rust_name)
+    }
+
+    /// Field of the class struct holding this virtual method
+    pub(crate) fn generate_vfunc_slot(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.vfunc {
+            return None;
+        }
+
+        let slot = &self.rust_name;
+        let params = self.generate_ffi_params();
+        let out_params = self.return_info.generate_out_params();
+        let error_param = if self.is_fallible {
+            quote! { error: *mut *mut ::glib::ffi::GError, }
+        } else {
+            quote! {}
+        };
+        let return_type = &self.return_info.c_type;
+        Some(quote! {
+            pub #slot: Option<unsafe extern "C" fn(#params #out_params #error_param) -> #return_type>
+        })
+    }
+
+    /// Points the class struct slot at the Rust implementation
+    pub(crate) fn generate_vfunc_init(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.vfunc {
+            return None;
+        }
+
+        let slot = &self.rust_name;
+        let real_name = self.vfunc_real_name();
+        Some(quote! { self.#slot = Some(#real_name); })
+    }
+
+    /// Class struct field declaration and its gtk-doc line
+    pub(crate) fn generate_vfunc_header(&self) -> Option<(String, String)> {
+        if !self.vfunc {
+            return None;
         }
+
+        let field = format!(
+            "    {} (*{})({});\n",
+            self.return_info.to_header_type(),
+            self.rust_name,
+            self.header_params().join(", ")
+        );
+        let overriders = if self.ffi_type == FfiType::Interface {
+            "implementations"
+        } else {
+            "subclasses"
+        };
+        let doc = format!(
+            " * @{}: the implementation of {}(), which {} may override\n",
+            self.rust_name, self.ffi_prefix, overriders
+        );
+        Some((field, doc))
     }
 
     pub(crate) fn generate_async(self) -> proc_macro2::TokenStream {
@@ -511,8 +3306,10 @@ impl FfiMethod {
         let conversions = self.generate_conversions();
         let body = self.generate_body();
         let return_type = &self.return_info.c_type;
-        let ok_handler = self.return_info.generate_ok_handler();
+        let ok_handler = self.return_info.generate_ok_handler(&self.ffi_prefix);
         let err_handler = self.return_info.generate_err_handler();
+        let out_params = self.return_info.generate_out_params();
+        let out_args = self.return_info.generate_out_args();
 
         let cancellable_ident = syn::Ident::new("cancellable", proc_macro2::Span::call_site());
         let cancellable_type: syn::Type =
@@ -526,22 +3323,45 @@ impl FfiMethod {
 
         let async_result_to_c = TransferMode::None.convert_to(quote! { async_result });
         let callback_result_to_c = TransferMode::None
-            .convert_to(quote! { callback_data.result.expect("callback was not called") });
+            .convert_to(quote! { callback_data.result.as_ref().expect("callback was not called") });
 
         let is_void = self.return_info.is_void();
+        let boxes_task_value = self.return_info.boxes_task_value();
+        let ok_type = &self.return_info.rust_type;
         let task_type = if is_void {
             quote! { bool }
+        } else if boxes_task_value {
+            quote! { ::glib::BoxedAnyObject }
         } else {
-            let ok_type = &self.return_info.rust_type;
             quote! { #ok_type }
         };
+        // `gio::Task` requires a `Send` value, which `BoxedAnyObject` and
+        // some objects aren't. The task never leaves the main context it's
+        // created on, so a `LocalTask` is enough for those
+        let task = if self.return_info.needs_local_task() {
+            quote! { ::gio::LocalTask }
+        } else {
+            quote! { ::gio::Task }
+        };
+        // Values without a GValue representation travel through the task
+        // inside a BoxedAnyObject
+        let unbox_val = if boxes_task_value {
+            quote! {
+                let val = *val
+                    .replace(())
+                    .downcast::<#ok_type>()
+                    .expect("task value has the method's return type");
+            }
+        } else {
+            quote! {}
+        };
 
         let (source_object_for_task, callback_source_expr) = if ffi_type.is_gobject() {
             if self.is_constructor() {
                 let obj_conversion = TransferMode::None
                     .convert_to(quote! { ::glib::object::Cast::upcast_ref::<::glib::Object>(obj) });
                 (
-                    quote! { None::<&::gio::Cancellable> },
+                    quote! { None::<&::glib::Object> },
                     quote! {
                         let callback_source = task_result
                             .as_ref()
@@ -555,7 +3375,7 @@ impl FfiMethod {
                     quote! { ::glib::object::Cast::upcast_ref::<::glib::Object>(&source_for_task) },
                 );
                 (
-                    quote! { Some(&source_for_task) },
+                    quote! { Some(::glib::object::Cast::upcast_ref::<::glib::Object>(&source_for_task)) },
                     quote! {
                         let callback_source = #source_conversion;
                     },
@@ -564,7 +3384,7 @@ impl FfiMethod {
         } else {
             // For non-GObject types, always use None as source and null_mut for callback
             (
-                quote! { None::<&::gio::Cancellable> },
+                quote! { None::<&::glib::Object> },
                 quote! {
                     let callback_source = ::std::ptr::null_mut();
                 },
@@ -597,7 +3417,7 @@ impl FfiMethod {
         };
 
         let sync_error_arg = if self.is_fallible {
-            quote! { , error }
+            quote! { error }
         } else {
             quote! {}
         };
@@ -605,20 +3425,29 @@ impl FfiMethod {
         let task_result_expr = if self.is_fallible {
             if is_void {
                 quote! { result.map(|_| true) }
+            } else if boxes_task_value {
+                quote! { result.map(::glib::BoxedAnyObject::new) }
             } else {
                 quote! { result }
             }
         } else if is_void {
             quote! { { let _ = result; Ok(true) } }
+        } else if boxes_task_value {
+            quote! { Ok(::glib::BoxedAnyObject::new(result)) }
         } else {
             quote! { Ok(result) }
         };
 
+        // Infallible methods only fail on parameters rejected by `_async`
+        let finish_name_str = finish_fn_name.to_string();
         let finish_body = if self.is_fallible {
             quote! {
-                match ::glib::object::Cast::downcast::<::gio::Task<#task_type>>(result) {
+                match ::glib::object::Cast::downcast::<#task<#task_type>>(result) {
                     Ok(task) => match unsafe { task.propagate() } {
-                        Ok(val) => #ok_handler,
+                        Ok(val) => {
+                            #unbox_val
+                            #ok_handler
+                        }
                         Err(e) => {
                             if !error.is_null() {
                                 unsafe {
@@ -633,27 +3462,42 @@ impl FfiMethod {
             }
         } else if is_void {
             quote! {
-                match ::glib::object::Cast::downcast::<::gio::Task<#task_type>>(result) {
-                    Ok(task) => {
-                        let _ = unsafe { task.propagate() }.unwrap();
-                        #ok_handler
-                    }
+                match ::glib::object::Cast::downcast::<#task<#task_type>>(result) {
+                    Ok(task) => match unsafe { task.propagate() } {
+                        Ok(_) => {
+                            #ok_handler
+                        }
+                        Err(err) => {
+                            ::gobject_ffi::strings::critical(#finish_name_str, &err);
+                            #err_handler
+                        }
+                    },
                     Err(_) => #err_handler,
                 }
             }
         } else {
             quote! {
-                match ::glib::object::Cast::downcast::<::gio::Task<#task_type>>(result) {
-                    Ok(task) => {
-                        let val = unsafe { task.propagate() }.unwrap();
-                        #ok_handler
-                    }
+                match ::glib::object::Cast::downcast::<#task<#task_type>>(result) {
+                    Ok(task) => match unsafe { task.propagate() } {
+                        Ok(val) => {
+                            #unbox_val
+                            #ok_handler
+                        }
+                        Err(err) => {
+                            ::gobject_ffi::strings::critical(#finish_name_str, &err);
+                            #err_handler
+                        }
+                    },
                     Err(_) => #err_handler,
                 }
             }
         };
 
+        let callback_types = self.generate_callback_types();
+
         quote! {
+            #(#callback_types)*
+
             #[unsafe(no_mangle)]
             pub unsafe extern "C" fn #async_fn_name(
                 #params
@@ -693,12 +3537,17 @@ impl FfiMethod {
                         #task_result_expr
                     };
 
-                    let task = unsafe {
-                        ::gio::Task::new(
-                            #source_object_for_task,
-                            cancellable.as_ref(),
-                            |_task, _result| {},
-                        )
+                    // `Task::new()` asks for a `Send` source object, which
+                    // subclasses holding a `RefCell` aren't. The task is
+                    // completed on this main context without a callback,
+                    // so the source never leaves it
+                    let task: #task<#task_type> = unsafe {
+                        ::glib::translate::from_glib_full(::gio::ffi::g_task_new(
+                            ::glib::translate::ToGlibPtr::to_glib_none(&#source_object_for_task).0,
+                            ::glib::translate::ToGlibPtr::to_glib_none(&cancellable).0,
+                            None,
+                            ::std::ptr::null_mut(),
+                        ))
                     };
 
                     let async_result = ::glib::object::Cast::upcast_ref::<::gio::AsyncResult>(&task).clone();
@@ -721,6 +3570,7 @@ impl FfiMethod {
             pub unsafe extern "C" fn #finish_fn_name(
                 #finish_self_param
                 result: *mut ::gio::ffi::GAsyncResult,
+                #out_params
                 #error_param
             ) -> #return_type {
                 #result_conversion
@@ -732,6 +3582,7 @@ impl FfiMethod {
             pub unsafe extern "C" fn #sync_fn_name(
                 #params
                 cancellable: *mut ::gio::ffi::GCancellable,
+                #out_params
                 #error_param
             ) -> #return_type {
                 struct CallbackData {
@@ -770,23 +3621,180 @@ impl FfiMethod {
                 loop_.run();
 
                 let result = #callback_result_to_c;
-                unsafe { #finish_fn_name(#sync_self_param result #sync_error_arg) }
+                unsafe { #finish_fn_name(#sync_self_param result, #out_args #sync_error_arg) }
             }
         }
     }
 
+    /// Rust types of the callbacks taken by closure parameters
+    fn generate_callback_types(&self) -> Vec<proc_macro2::TokenStream> {
+        self.params
+            .iter()
+            .filter_map(|param| param.closure.as_ref())
+            .map(Closure::generate_typedef)
+            .collect()
+    }
+
     pub(crate) fn generate_header(&self) -> String {
-        if self.is_async {
+        let typedefs = self
+            .params
+            .iter()
+            .filter_map(|param| param.closure.as_ref())
+            .map(Closure::generate_header_typedef)
+            .collect::<String>();
+        let functions = if self.is_async {
             self.generate_async_header()
         } else {
             self.generate_sync_header()
-        }
+        };
+        typedefs + &functions + &self.generate_signal_header().unwrap_or_default()
     }
 
-    fn generate_sync_header(&self) -> String {
-        let fn_name = &self.ffi_prefix;
-        let return_type = self.return_info.to_header_type();
+    /// C name of the signal's handler typedef, such as
+    /// `CalculatorValueChangedHandler`
+    fn signal_handler_name(&self, signal: &str) -> String {
+        use heck::ToUpperCamelCase;
+
+        format!(
+            "{}{}Handler",
+            self.c_type_name,
+            signal.to_upper_camel_case()
+        )
+    }
+
+    fn signal_connect_name(&self, signal: &str) -> String {
+        use heck::ToSnakeCase;
+
+        let prefix = self
+            .ffi_prefix
+            .strip_suffix(&self.rust_name.to_string())
+            .expect("the prefix ends with the method name");
+        format!("{}connect_{}", prefix, signal.to_snake_case())
+    }
+
+    /// Handler typedef and `connect_` helper of the signal emitted by this
+    /// method
+    fn generate_signal(&self) -> Option<proc_macro2::TokenStream> {
+        let signal = self.signal.as_ref()?;
+        let c_type_name = &self.c_type_name;
+        let handler = syn::Ident::new(&self.signal_handler_name(signal), self.rust_name.span());
+        let connect_name = self.signal_connect_name(signal);
+        let connect_fn = syn::Ident::new(&connect_name, self.rust_name.span());
+        let param_types = self.params.iter().map(|param| &param.c_type);
+        let param_rust_types = self.params.iter().map(|param| &param.rust_type);
+        let return_rust_type = if self.return_info.is_void() {
+            quote! { () }
+        } else {
+            let rust_type = &self.return_info.rust_type;
+            quote! { #rust_type }
+        };
+        let return_type = if self.return_info.is_void() {
+            None
+        } else {
+            let c_type = &self.return_info.c_type;
+            Some(quote! { -> #c_type })
+        };
+
+        Some(quote! {
+            pub type #handler = unsafe extern "C" fn(
+                *mut #c_type_name,
+                #(#param_types,)*
+                ::glib::ffi::gpointer,
+            ) #return_type;
+
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn #connect_fn(
+                self_param: *mut #c_type_name,
+                handler: ::std::option::Option<#handler>,
+                user_data: ::glib::ffi::gpointer,
+                destroy_data: ::glib::gobject_ffi::GClosureNotify,
+            ) -> ::std::os::raw::c_ulong {
+                unsafe {
+                    ::gobject_ffi::signal::connect(
+                        #connect_name,
+                        self_param as *mut ::glib::gobject_ffi::GObject,
+                        #signal,
+                        ::gobject_ffi::signal::HandlerSignature {
+                            param_types: &[#(<#param_rust_types as ::glib::prelude::StaticType>::static_type()),*],
+                            return_type: <#return_rust_type as ::glib::prelude::StaticType>::static_type(),
+                        },
+                        ::std::mem::transmute::<
+                            ::std::option::Option<#handler>,
+                            ::glib::gobject_ffi::GCallback,
+                        >(handler),
+                        user_data,
+                        destroy_data,
+                    )
+                }
+            }
+        })
+    }
+
+    /// Signal docs, handler typedef and `connect_` helper declaration
+    fn generate_signal_header(&self) -> Option<String> {
+        use heck::ToSnakeCase;
+
+        let signal = self.signal.as_ref()?;
+        let c_type_name = self.c_type_name.to_string();
+        let handler = self.signal_handler_name(signal);
+        let connect_name = self.signal_connect_name(signal);
+        let param_docs = self
+            .params
+            .iter()
+            .map(|param| format!("{}\n", param.to_gtk_doc_annotation(&c_type_name)))
+            .collect::<String>();
+        let returns = self
+            .return_info
+            .to_gtk_doc_returns()
+            .map(|returns| format!(" *\n{}\n", returns))
+            .unwrap_or_default();
+
+        let mut header = format!(
+            "\n/**\n * {}::{}:\n * @{}: the object which received the signal\n{}{} *\n * Emitted by {}().\n */\n\n",
+            c_type_name,
+            signal,
+            c_type_name.to_snake_case(),
+            param_docs,
+            returns,
+            self.ffi_prefix
+        );
+
+        let params = std::iter::once(format!("{}* self", c_type_name))
+            .chain(self.params.iter().map(FfiParam::to_header_param))
+            .chain(std::iter::once("gpointer user_data".to_string()))
+            .collect::<Vec<_>>();
+        header.push_str(&format!(
+            "/**\n * {}:\n * @self: the #{} emitting #{}::{}\n{} * @user_data: the data passed to {}()\n{} */\ntypedef {} (*{})({});\n\n",
+            handler,
+            c_type_name,
+            c_type_name,
+            signal,
+            param_docs,
+            connect_name,
+            returns,
+            self.return_info.to_header_type(),
+            handler,
+            params.join(", ")
+        ));
+
+        header.push_str(&format!(
+            "/**\n * {}:\n * @self: a #{}\n * @handler: (scope notified) (closure user_data) (destroy destroy_data) {}\n * @user_data: (closure) gpointer\n * @destroy_data: (nullable) GClosureNotify, called on @user_data once @handler is disconnected\n *\n * Connects @handler to #{}::{} like g_signal_connect_data().\n *\n * Returns: the handler ID, or 0 if @handler could not be connected\n */\ngulong {}({}* self, {} handler, gpointer user_data, GClosureNotify destroy_data);\n",
+            connect_name,
+            c_type_name,
+            handler,
+            c_type_name,
+            signal,
+            connect_name,
+            c_type_name,
+            handler
+        ));
+
+        Some(header)
+    }
 
+    /// C parameters of the synchronous function, shared with its class
+    /// struct slot
+    fn header_params(&self) -> Vec<String> {
         let mut params = Vec::new();
 
         if let Some(self_c_type) = self.generate_self_c_type_string() {
@@ -797,13 +3805,30 @@ impl FfiMethod {
             params.push(param.to_header_param());
         }
 
+        params.extend(self.return_info.to_header_out_params());
+
         if self.is_fallible {
             params.push("GError** error".to_string());
         }
 
-        let params_str = params.join(", ");
+        params
+    }
+
+    fn generate_sync_header(&self) -> String {
+        let fn_name = &self.ffi_prefix;
+        let return_type = self.return_info.to_header_type();
+        let params_str = self.header_params().join(", ");
 
-        let mut doc = format!("/**\n * {}:\n", fn_name);
+        let mut doc = match self.property {
+            Some(PropertyAccess::Get(ref name)) => {
+                format!("/**\n * {}: (get-property {})\n", fn_name, name)
+            }
+            Some(PropertyAccess::Set(ref name)) => {
+                format!("/**\n * {}: (set-property {})\n", fn_name, name)
+            }
+            None if self.vfunc => format!("/**\n * {}: (virtual {})\n", fn_name, self.rust_name),
+            None => format!("/**\n * {}:\n", fn_name),
+        };
 
         if !self.is_constructor() {
             doc.push_str(&format!(" * @self: a #{}\n", self.c_type_name));
@@ -816,6 +3841,10 @@ impl FfiMethod {
             ));
         }
 
+        for out_param in self.return_info.to_gtk_doc_out_params() {
+            doc.push_str(&format!("{}\n", out_param));
+        }
+
         if self.is_fallible {
             doc.push_str(" * @error: (out) (optional): return location for error\n");
         }
@@ -878,6 +3907,7 @@ impl FfiMethod {
             finish_params.push(format!("{}* self", self_c_type));
         }
         finish_params.push("GAsyncResult* result".to_string());
+        finish_params.extend(self.return_info.to_header_out_params());
         if self.is_fallible {
             finish_params.push("GError** error".to_string());
         }
@@ -887,6 +3917,9 @@ impl FfiMethod {
             finish_doc.push_str(&format!(" * @self: a #{}\n", self.c_type_name));
         }
         finish_doc.push_str(" * @result: a #GAsyncResult\n");
+        for out_param in self.return_info.to_gtk_doc_out_params() {
+            finish_doc.push_str(&format!("{}\n", out_param));
+        }
         if self.is_fallible {
             finish_doc.push_str(" * @error: (out) (optional): return location for error\n");
         }
@@ -911,6 +3944,7 @@ impl FfiMethod {
             sync_params.push(param.to_header_param());
         }
         sync_params.push("GCancellable* cancellable".to_string());
+        sync_params.extend(self.return_info.to_header_out_params());
         if self.is_fallible {
             sync_params.push("GError** error".to_string());
         }
@@ -926,6 +3960,9 @@ impl FfiMethod {
             ));
         }
         sync_doc.push_str(" * @cancellable: (nullable): a #GCancellable\n");
+        for out_param in self.return_info.to_gtk_doc_out_params() {
+            sync_doc.push_str(&format!("{}\n", out_param));
+        }
         if self.is_fallible {
             sync_doc.push_str(" * @error: (out) (optional): return location for error\n");
         }
@@ -950,6 +3987,8 @@ impl FfiMethod {
 
         if let Some(c_type) = self.ffi_type.self_c_type() {
             Some(crate::utils::rust_type_to_c_type_string(&c_type))
+        } else if self.ffi_type == FfiType::Struct && !self.mut_self {
+            Some(format!("const {}", self.c_type_name))
         } else {
             Some(self.c_type_name.to_string())
         }
@@ -9,29 +9,41 @@ use syn::{
 pub(crate) enum FfiType {
     #[default]
     Object,
+    Interface,
     Boxed,
     Shared,
     Enum,
     Flags,
+    /// `#[repr(C)]` plain old data passed by pointer, with its fields
+    /// visible to C
+    Struct,
 }
 
 impl FfiType {
     pub(crate) fn is_gobject(&self) -> bool {
-        matches!(self, FfiType::Object)
+        matches!(self, FfiType::Object | FfiType::Interface)
     }
 
     pub(crate) fn self_c_type(&self) -> Option<syn::Type> {
         match self {
             FfiType::Enum => Some(syn::parse_quote! { i32 }),
             FfiType::Flags => Some(syn::parse_quote! { u32 }),
-            FfiType::Object | FfiType::Boxed | FfiType::Shared => None,
+            FfiType::Object
+            | FfiType::Interface
+            | FfiType::Boxed
+            | FfiType::Shared
+            | FfiType::Struct => None,
         }
     }
 
     pub(crate) fn self_transfer_mode(&self) -> TransferMode {
         match self {
             FfiType::Enum | FfiType::Flags => TransferMode::Primitive,
-            FfiType::Object | FfiType::Boxed | FfiType::Shared => TransferMode::None,
+            FfiType::Object
+            | FfiType::Interface
+            | FfiType::Boxed
+            | FfiType::Shared
+            | FfiType::Struct => TransferMode::None,
         }
     }
 }
@@ -40,6 +52,7 @@ impl FfiType {
 pub(crate) enum TransferMode {
     Primitive,
     None,
+    Container,
     Full,
 }
 
@@ -56,6 +69,9 @@ impl TransferMode {
             TransferMode::None => quote! {
                 let #param_name: #ty = unsafe { ::glib::translate::FromGlibPtrNone::from_glib_none(#param_name) };
             },
+            TransferMode::Container => quote! {
+                let #param_name: #ty = unsafe { ::glib::translate::FromGlibPtrContainer::from_glib_container(#param_name) };
+            },
             TransferMode::Full => quote! {
                 let #param_name: #ty = unsafe { ::glib::translate::FromGlibPtrFull::from_glib_full(#param_name) };
             },
@@ -66,6 +82,9 @@ impl TransferMode {
         match self {
             TransferMode::Primitive => quote! { ::glib::translate::IntoGlib::into_glib(#val) },
             TransferMode::None => quote! { ::glib::translate::ToGlibPtr::to_glib_none(&#val).0 },
+            TransferMode::Container => {
+                quote! { ::glib::translate::ToGlibPtr::to_glib_container(&#val).0 }
+            }
             TransferMode::Full => quote! { ::glib::translate::ToGlibPtr::to_glib_full(&#val) },
         }
     }
@@ -73,7 +92,9 @@ impl TransferMode {
     pub(crate) fn error_value(&self) -> proc_macro2::TokenStream {
         match self {
             TransferMode::Primitive => quote! { 0 },
-            TransferMode::None | TransferMode::Full => quote! { ::std::ptr::null_mut() },
+            TransferMode::None | TransferMode::Container | TransferMode::Full => {
+                quote! { ::std::ptr::null_mut() }
+            }
         }
     }
 
@@ -81,11 +102,30 @@ impl TransferMode {
         match self {
             TransferMode::Primitive => None,
             TransferMode::None => Some("transfer none"),
+            TransferMode::Container => Some("transfer container"),
             TransferMode::Full => Some("transfer full"),
         }
     }
 }
 
+/// GLib container a `Vec` or slice is passed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Collection {
+    /// A C array, with a length parameter or zero-terminated
+    Array,
+    List,
+    PtrArray,
+}
+
+/// Unit a `Duration` is passed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DurationUnit {
+    /// `guint` milliseconds
+    Millis,
+    /// `gint64` microseconds, the default
+    Micros,
+}
+
 pub(crate) struct CTypeOverride {
     pub(crate) c_type: Type,
     pub(crate) transfer: TransferMode,
@@ -115,11 +155,12 @@ impl Parse for CTypeOverride {
         let transfer = match mode_ident.to_string().as_str() {
             "primitive" => TransferMode::Primitive,
             "none" => TransferMode::None,
+            "container" => TransferMode::Container,
             "full" => TransferMode::Full,
             _ => {
                 return Err(syn::Error::new_spanned(
                     mode_ident,
-                    "expected `primitive`, `none`, or `full`",
+                    "expected `primitive`, `none`, `container`, or `full`",
                 ));
             }
         };
@@ -128,11 +169,341 @@ impl Parse for CTypeOverride {
     }
 }
 
+/// Options passed through `#[ffi(...)]` on methods and parameters
+#[derive(Default)]
+pub(crate) struct FfiAttrs {
+    /// Use the `c_error_value()` sentinel for `Option<primitive>` instead of
+    /// a nullable pointer / out parameter
+    pub(crate) sentinel: bool,
+    /// C value returned when a fallible or async method fails
+    pub(crate) error_value: Option<syn::Expr>,
+    /// Names of the tuple elements returned through `out_<name>` parameters
+    pub(crate) out_names: Option<Vec<syn::Ident>>,
+    /// Return the first tuple element directly instead of through an out
+    /// parameter
+    pub(crate) return_first: bool,
+    /// Start a `&mut T` from a default value instead of the caller's, the
+    /// default for non-numbers
+    pub(crate) out: bool,
+    /// Read the caller's value into a `&mut T` before the call, the default
+    /// for numbers
+    pub(crate) inout: bool,
+    /// The caller provides the storage a `&mut T` points to
+    pub(crate) caller_allocates: bool,
+    /// Ownership of an array parameter passed by the caller
+    pub(crate) transfer: Option<TransferMode>,
+    /// Pass an array without a length, terminated by a zero element. String
+    /// arrays default to `true` (`GStrv`)
+    pub(crate) zero_terminated: Option<bool>,
+    /// Pass a `Vec` or slice as a `GList` or `GPtrArray` instead of a C array
+    pub(crate) collection: Option<Collection>,
+    /// Unit of a `Duration` parameter or return value
+    pub(crate) duration: Option<DurationUnit>,
+    /// Pass a `GVariant*` checked against this type string, or against the
+    /// static variant type of a non-`glib::Variant` parameter when `None`
+    pub(crate) variant: Option<Option<syn::LitStr>>,
+    /// Export a `connect_` helper for the signal this method emits, named
+    /// after the method unless given
+    pub(crate) signal: Option<Option<syn::LitStr>>,
+    /// Dispatch the C function through a slot in the class struct that C
+    /// subclasses can override
+    pub(crate) vfunc: bool,
+    /// The caller's `user_data` for a `Send`/`Sync` closure may be used from
+    /// other threads
+    pub(crate) thread_safe: bool,
+}
+
+impl FfiAttrs {
+    pub(crate) fn merge(&mut self, other: FfiAttrs) {
+        self.sentinel |= other.sentinel;
+        if other.error_value.is_some() {
+            self.error_value = other.error_value;
+        }
+        if other.out_names.is_some() {
+            self.out_names = other.out_names;
+        }
+        self.return_first |= other.return_first;
+        self.out |= other.out;
+        self.inout |= other.inout;
+        self.caller_allocates |= other.caller_allocates;
+        if other.transfer.is_some() {
+            self.transfer = other.transfer;
+        }
+        if other.zero_terminated.is_some() {
+            self.zero_terminated = other.zero_terminated;
+        }
+        if other.collection.is_some() {
+            self.collection = other.collection;
+        }
+        if other.duration.is_some() {
+            self.duration = other.duration;
+        }
+        if other.variant.is_some() {
+            self.variant = other.variant;
+        }
+        if other.signal.is_some() {
+            self.signal = other.signal;
+        }
+        self.vfunc |= other.vfunc;
+        self.thread_safe |= other.thread_safe;
+    }
+}
+
+impl Parse for FfiAttrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = FfiAttrs::default();
+
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+
+            match key.to_string().as_str() {
+                "sentinel" => attrs.sentinel = true,
+                "error_value" => {
+                    input.parse::<Token![=]>()?;
+                    attrs.error_value = Some(input.parse()?);
+                }
+                "out_names" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let names = content.parse_terminated(syn::Ident::parse, Token![,])?;
+                    attrs.out_names = Some(names.into_iter().collect());
+                }
+                "return_first" => attrs.return_first = true,
+                "out" => attrs.out = true,
+                "inout" => attrs.inout = true,
+                "caller_allocates" => attrs.caller_allocates = true,
+                "thread_safe" => attrs.thread_safe = true,
+                "transfer" => {
+                    input.parse::<Token![=]>()?;
+                    let mode_ident: syn::Ident = input.parse()?;
+                    attrs.transfer = Some(match mode_ident.to_string().as_str() {
+                        "none" => TransferMode::None,
+                        "container" => TransferMode::Container,
+                        "full" => TransferMode::Full,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                mode_ident,
+                                "expected `none`, `container`, or `full`",
+                            ));
+                        }
+                    });
+                }
+                "zero_terminated" => {
+                    let value = if input.parse::<Token![=]>().is_ok() {
+                        input.parse::<syn::LitBool>()?.value
+                    } else {
+                        true
+                    };
+                    attrs.zero_terminated = Some(value);
+                }
+                "collection" => {
+                    input.parse::<Token![=]>()?;
+                    let collection_ident: syn::Ident = input.parse()?;
+                    attrs.collection = Some(match collection_ident.to_string().as_str() {
+                        "array" => Collection::Array,
+                        "list" => Collection::List,
+                        "ptr_array" => Collection::PtrArray,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                collection_ident,
+                                "expected `array`, `list`, or `ptr_array`",
+                            ));
+                        }
+                    });
+                }
+                "duration" => {
+                    input.parse::<Token![=]>()?;
+                    let unit_ident: syn::Ident = input.parse()?;
+                    attrs.duration = Some(match unit_ident.to_string().as_str() {
+                        "millis" => DurationUnit::Millis,
+                        "micros" => DurationUnit::Micros,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                unit_ident,
+                                "expected `millis` or `micros`",
+                            ));
+                        }
+                    });
+                }
+                "variant" => {
+                    let type_string = if input.parse::<Token![=]>().is_ok() {
+                        let type_string: syn::LitStr = input.parse()?;
+                        if !crate::utils::is_valid_variant_type(&type_string.value()) {
+                            return Err(syn::Error::new_spanned(
+                                type_string,
+                                "invalid variant type string",
+                            ));
+                        }
+                        Some(type_string)
+                    } else {
+                        None
+                    };
+                    attrs.variant = Some(type_string);
+                }
+                "signal" => {
+                    let name = if input.parse::<Token![=]>().is_ok() {
+                        let name: syn::LitStr = input.parse()?;
+                        let value = name.value();
+                        let valid = value.starts_with(|c: char| c.is_ascii_alphabetic())
+                            && value
+                                .chars()
+                                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                        if !valid {
+                            return Err(syn::Error::new_spanned(name, "invalid signal name"));
+                        }
+                        Some(name)
+                    } else {
+                        None
+                    };
+                    attrs.signal = Some(name);
+                }
+                "vfunc" => attrs.vfunc = true,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        "expected `sentinel`, `error_value`, `out_names`, `return_first`, `out`, `inout`, `caller_allocates`, `transfer`, `zero_terminated`, `collection`, `duration`, `variant`, `signal`, `vfunc` or `thread_safe`",
+                    ));
+                }
+            }
+
+            if input.parse::<Token![,]>().is_err() {
+                break;
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// A type named in `parent = ...` or `implements(...)`, written as its Rust
+/// path, optionally followed by `as "CName"` and `in "header.h"` when they
+/// can't be inferred from the crate
+pub(crate) struct TypeRef {
+    pub(crate) path: syn::Path,
+    pub(crate) c_type_name: String,
+    /// `#include` argument for the header declaring the type, unless already
+    /// included by the generated header
+    pub(crate) header: Option<String>,
+}
+
+impl Parse for TypeRef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: syn::Path = input.parse()?;
+        let (mut c_type_name, mut header) = crate::utils::known_c_type(&path);
+
+        if input.parse::<Token![as]>().is_ok() {
+            c_type_name = input.parse::<syn::LitStr>()?.value();
+        }
+        if input.parse::<Token![in]>().is_ok() {
+            header = Some(format!("\"{}\"", input.parse::<syn::LitStr>()?.value()));
+        }
+
+        Ok(TypeRef {
+            path,
+            c_type_name,
+            header,
+        })
+    }
+}
+
+/// A property declared in `#[ffi_impl(properties(...))]`, written like a
+/// `#[derive(glib::Properties)]` field
+pub(crate) struct FfiProperty {
+    pub(crate) ident: syn::Ident,
+    pub(crate) ty: Type,
+    /// GObject name of the property, `ident` in kebab-case unless renamed
+    pub(crate) name: String,
+    pub(crate) readable: bool,
+    pub(crate) writable: bool,
+    pub(crate) construct: bool,
+    pub(crate) construct_only: bool,
+}
+
+impl Parse for FfiProperty {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let ident: syn::Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+
+        let mut property = FfiProperty {
+            name: ident.to_string().replace('_', "-"),
+            ident,
+            ty,
+            readable: false,
+            writable: false,
+            construct: false,
+            construct_only: false,
+        };
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("property")) {
+            // Options that don't change the C API, such as `default` or
+            // `minimum`, are accepted and ignored
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    property.name = meta.value()?.parse::<syn::LitStr>()?.value();
+                    return Ok(());
+                }
+                if meta.path.is_ident("get") {
+                    property.readable = true;
+                } else if meta.path.is_ident("set") {
+                    property.writable = true;
+                } else if meta.path.is_ident("construct") {
+                    property.construct = true;
+                } else if meta.path.is_ident("construct_only") {
+                    property.construct_only = true;
+                    property.writable = true;
+                }
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<proc_macro2::TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(property)
+    }
+}
+
+/// A field of a `ty = "struct"` record declared in
+/// `#[ffi_impl(fields(...))]`, in declaration order
+pub(crate) struct FfiField {
+    pub(crate) ident: syn::Ident,
+    pub(crate) ty: Type,
+    /// `///` comment, used as the field's gtk-doc description
+    pub(crate) doc: Option<String>,
+}
+
+impl Parse for FfiField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let ident: syn::Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+
+        let doc = crate::utils::doc_string(&attrs);
+        Ok(FfiField { ident, ty, doc })
+    }
+}
+
 pub(crate) struct FfiImplArgs {
     pub(crate) c_type_name: Option<syn::LitStr>,
     pub(crate) prefix: syn::LitStr,
     pub(crate) ty: FfiType,
     pub(crate) generate_header: Option<syn::LitStr>,
+    pub(crate) properties: Vec<FfiProperty>,
+    /// Parent class, checked against `ObjectSubclass::ParentType`
+    pub(crate) parent: Option<TypeRef>,
+    /// Interfaces the object implements, or prerequisites of an interface
+    pub(crate) implements: Vec<TypeRef>,
+    /// Fields of a `ty = "struct"` record, checked against the Rust layout
+    pub(crate) fields: Vec<FfiField>,
+    /// Variants of a boxed Rust enum exported as a tagged union, written
+    /// like the enum's own variants
+    pub(crate) variants: Vec<syn::Variant>,
 }
 
 impl Parse for FfiImplArgs {
@@ -141,9 +512,31 @@ impl Parse for FfiImplArgs {
         let mut prefix: Option<syn::LitStr> = None;
         let mut ty: Option<FfiType> = None;
         let mut generate_header: Option<syn::LitStr> = None;
+        let mut properties = Vec::new();
+        let mut parent = None;
+        let mut implements = Vec::new();
+        let mut fields = Vec::new();
+        let mut variants = Vec::new();
 
         while !input.is_empty() {
             let key: syn::Ident = input.parse()?;
+            if key == "properties" || key == "implements" || key == "fields" || key == "variants" {
+                let content;
+                syn::parenthesized!(content in input);
+                if key == "properties" {
+                    properties.extend(content.parse_terminated(FfiProperty::parse, Token![,])?);
+                } else if key == "implements" {
+                    implements.extend(content.parse_terminated(TypeRef::parse, Token![,])?);
+                } else if key == "fields" {
+                    fields.extend(content.parse_terminated(FfiField::parse, Token![,])?);
+                } else {
+                    variants.extend(content.parse_terminated(syn::Variant::parse, Token![,])?);
+                }
+                if input.parse::<Token![,]>().is_err() {
+                    break;
+                }
+                continue;
+            }
             input.parse::<Token![=]>()?;
 
             match key.to_string().as_str() {
@@ -151,6 +544,9 @@ impl Parse for FfiImplArgs {
                     let value: syn::LitStr = input.parse()?;
                     c_type_name = Some(value);
                 }
+                "parent" => {
+                    parent = Some(input.parse()?);
+                }
                 "prefix" => {
                     let value: syn::LitStr = input.parse()?;
                     prefix = Some(value);
@@ -159,14 +555,16 @@ impl Parse for FfiImplArgs {
                     let ty_value: syn::LitStr = input.parse()?;
                     ty = Some(match ty_value.value().as_str() {
                         "object" => FfiType::Object,
+                        "interface" => FfiType::Interface,
                         "boxed" => FfiType::Boxed,
                         "shared" => FfiType::Shared,
                         "enum" => FfiType::Enum,
                         "flags" => FfiType::Flags,
+                        "struct" => FfiType::Struct,
                         _ => {
                             return Err(syn::Error::new_spanned(
                                 ty_value,
-                                "expected one of: \"object\", \"boxed\", \"shared\", \"enum\", \"flags\"",
+                                "expected one of: \"object\", \"interface\", \"boxed\", \"shared\", \"enum\", \"flags\", \"struct\"",
                             ));
                         }
                     });
@@ -178,7 +576,7 @@ impl Parse for FfiImplArgs {
                 _ => {
                     return Err(syn::Error::new_spanned(
                         key,
-                        "expected `c_type_name`, `prefix`, `ty`, or `generate_header`",
+                        "expected `c_type_name`, `prefix`, `ty`, `generate_header`, `properties`, `parent`, `implements`, `fields`, or `variants`",
                     ));
                 }
             }
@@ -198,6 +596,11 @@ impl Parse for FfiImplArgs {
             prefix,
             ty,
             generate_header,
+            properties,
+            parent,
+            implements,
+            fields,
+            variants,
         })
     }
 }
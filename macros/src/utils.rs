@@ -1,7 +1,35 @@
 use quote::quote;
 use syn::{Attribute, Type, parse::Parse};
 
-use crate::types::CTypeOverride;
+use crate::types::{CTypeOverride, DurationUnit, FfiAttrs};
+
+/// C name and header of a type from a known GNOME crate, such as
+/// `GtkWidget` and `<gtk/gtk.h>` for `gtk::Widget`. Other types keep their
+/// Rust name and need no extra header
+pub fn known_c_type(path: &syn::Path) -> (String, Option<String>) {
+    let name = path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let crate_name = match path.segments.first() {
+        Some(first) if path.segments.len() > 1 => first.ident.to_string(),
+        _ => return (name, None),
+    };
+
+    let (namespace, header) = match crate_name.as_str() {
+        // Already included by every generated header
+        "glib" | "gio" => ("G", None),
+        "gtk" | "gtk4" => ("Gtk", Some("<gtk/gtk.h>")),
+        "gdk" | "gdk4" => ("Gdk", Some("<gdk/gdk.h>")),
+        "adw" | "libadwaita" => ("Adw", Some("<adwaita.h>")),
+        "gst" | "gstreamer" => ("Gst", Some("<gst/gst.h>")),
+        "pango" => ("Pango", Some("<pango/pango.h>")),
+        "soup" => ("Soup", Some("<libsoup/soup.h>")),
+        _ => return (name, None),
+    };
+    (format!("{}{}", namespace, name), header.map(str::to_string))
+}
 
 pub fn is_unit_type(ty: &Type) -> bool {
     matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
@@ -36,6 +64,50 @@ pub fn extract_option_inner(ty: &Type) -> Option<&Type> {
     }
 }
 
+/// Returns the inner type of `Option<primitive>`, which is passed as a
+/// nullable pointer rather than through the `c_error_value()` sentinel
+pub(crate) fn extract_nullable_primitive(ty: &Type) -> Option<&Type> {
+    extract_option_inner(ty).filter(|inner| is_primitive_type(inner))
+}
+
+/// Returns the element types of a non-empty tuple
+pub(crate) fn extract_tuple_elems(ty: &Type) -> Option<Vec<Type>> {
+    match ty {
+        Type::Tuple(tuple) if !tuple.elems.is_empty() => {
+            Some(tuple.elems.iter().cloned().collect())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the element type of `&[T]` or `Vec<T>`, and whether it is a slice
+pub(crate) fn extract_array_elem(ty: &Type) -> Option<(&Type, bool)> {
+    if let Type::Reference(r) = ty {
+        if let (None, Type::Slice(slice)) = (r.mutability, &*r.elem) {
+            return Some((&slice.elem, true));
+        }
+        return None;
+    }
+
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(elem)) => Some((elem, false)),
+        _ => None,
+    }
+}
+
 pub fn is_mutable_reference(ty: &Type) -> bool {
     matches!(ty, Type::Reference(r) if r.mutability.is_some())
 }
@@ -80,7 +152,11 @@ pub fn rust_type_to_c_type(ty: &Type) -> proc_macro2::TokenStream {
     }
 
     if let Some(inner_type) = extract_option_inner(ty) {
-        return quote! { <#inner_type as ::gobject_ffi::FfiConvert>::CType };
+        return rust_type_to_c_type(inner_type);
+    }
+
+    if let Type::Reference(r) = ty {
+        return rust_type_to_c_type(&r.elem);
     }
 
     quote! { <#ty as ::gobject_ffi::FfiConvert>::CType }
@@ -94,6 +170,14 @@ pub(crate) fn extract_c_type(attrs: &[Attribute]) -> syn::Result<Option<CTypeOve
     extract_attribute(attrs, "c_type")
 }
 
+pub(crate) fn extract_ffi_attrs(attrs: &[Attribute]) -> syn::Result<FfiAttrs> {
+    let mut ffi_attrs = FfiAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("ffi")) {
+        ffi_attrs.merge(attr.parse_args::<FfiAttrs>()?);
+    }
+    Ok(ffi_attrs)
+}
+
 fn extract_attribute<T: Parse>(attrs: &[Attribute], name: &str) -> syn::Result<Option<T>> {
     attrs
         .iter()
@@ -102,6 +186,98 @@ fn extract_attribute<T: Parse>(attrs: &[Attribute], name: &str) -> syn::Result<O
         .transpose()
 }
 
+/// `///` comments joined into a single line, for gtk-doc descriptions
+pub(crate) fn doc_string(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// The C spelling of an `error_value`, such as `G_MININT32` for `i32::MIN`,
+/// for the `Returns:` documentation
+pub(crate) fn c_value_string(expr: &syn::Expr) -> syn::Result<String> {
+    let unsupported = || {
+        syn::Error::new_spanned(
+            expr,
+            "`error_value` must be a literal, a numeric constant such as `i32::MIN` or `glib::ffi::GTRUE`/`GFALSE`",
+        )
+    };
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+            syn::Lit::Int(int) => Ok(int.base10_digits().to_string()),
+            syn::Lit::Float(float) => Ok(float.base10_digits().to_string()),
+            syn::Lit::Bool(value) => Ok(if value.value { "TRUE" } else { "FALSE" }.to_string()),
+            _ => Err(unsupported()),
+        },
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) => {
+            let value = c_value_string(inner)?;
+            Ok(match value.strip_prefix('-') {
+                Some(value) => value.to_string(),
+                None => format!("-{}", value),
+            })
+        }
+        syn::Expr::Paren(paren) => c_value_string(&paren.expr),
+        syn::Expr::Path(path) => {
+            let segments = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>();
+            let [.., ty, constant] = segments.as_slice() else {
+                return Err(unsupported());
+            };
+            let c_value = match (ty.as_str(), constant.as_str()) {
+                ("ffi", "GTRUE") => "TRUE",
+                ("ffi", "GFALSE") => "FALSE",
+                ("u8" | "u16" | "u32" | "u64" | "usize", "MIN") => "0",
+                ("i8", "MIN") => "G_MININT8",
+                ("i8", "MAX") => "G_MAXINT8",
+                ("u8", "MAX") => "G_MAXUINT8",
+                ("i16", "MIN") => "G_MININT16",
+                ("i16", "MAX") => "G_MAXINT16",
+                ("u16", "MAX") => "G_MAXUINT16",
+                ("i32", "MIN") => "G_MININT32",
+                ("i32", "MAX") => "G_MAXINT32",
+                ("u32", "MAX") => "G_MAXUINT32",
+                ("i64", "MIN") => "G_MININT64",
+                ("i64", "MAX") => "G_MAXINT64",
+                ("u64", "MAX") => "G_MAXUINT64",
+                ("isize", "MIN") => "G_MINSSIZE",
+                ("isize", "MAX") => "G_MAXSSIZE",
+                ("usize", "MAX") => "G_MAXSIZE",
+                ("f32", "MAX") => "G_MAXFLOAT",
+                ("f32", "MIN") => "-G_MAXFLOAT",
+                ("f64", "MAX") => "G_MAXDOUBLE",
+                ("f64", "MIN") => "-G_MAXDOUBLE",
+                ("f32" | "f64", "NAN") => "NAN",
+                ("f32" | "f64", "INFINITY") => "INFINITY",
+                ("f32" | "f64", "NEG_INFINITY") => "-INFINITY",
+                _ => return Err(unsupported()),
+            };
+            Ok(c_value.to_string())
+        }
+        _ => Err(unsupported()),
+    }
+}
+
 pub(crate) fn check_fallibility(return_type: &syn::ReturnType) -> bool {
     if let syn::ReturnType::Type(_, ty) = return_type {
         is_result_type(ty)
@@ -135,6 +311,292 @@ pub(crate) fn is_primitive_type(ty: &Type) -> bool {
     false
 }
 
+pub(crate) fn last_segment_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// `String`, `GString` or `&str`
+pub(crate) fn is_string_type(ty: &Type) -> bool {
+    let ty = match ty {
+        Type::Reference(r) if r.mutability.is_none() => &*r.elem,
+        _ => ty,
+    };
+    matches!(
+        last_segment_ident(ty).as_deref(),
+        Some("String" | "GString" | "str")
+    )
+}
+
+/// `&str`, which is converted through an owned `String`
+pub(crate) fn is_str_ref(ty: &Type) -> bool {
+    matches!(ty, Type::Reference(r) if r.mutability.is_none() && is_string_type(&r.elem))
+}
+
+/// Types whose `FfiConvert` representation is a `GStrv`: `glib::StrV`,
+/// `Vec<String>` and `Vec<GString>`, optionally wrapped in `Option`
+pub(crate) fn is_strv_type(ty: &Type) -> bool {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    if last_segment_ident(ty).as_deref() == Some("StrV") {
+        return true;
+    }
+    matches!(extract_array_elem(ty), Some((elem, false)) if is_string_type(elem) && !is_str_ref(elem))
+}
+
+/// `Vec<u8>`, optionally wrapped in `Option`, whose `FfiConvert`
+/// representation is a `GBytes` wrapping the buffer
+pub(crate) fn is_byte_vec(ty: &Type) -> bool {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    matches!(extract_array_elem(ty), Some((elem, false)) if last_segment_ident(elem).as_deref() == Some("u8"))
+}
+
+/// Returns the key and value types of `HashMap<K, V>` or `BTreeMap<K, V>`
+pub(crate) fn extract_map_types(ty: &Type) -> Option<(&Type, &Type)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    Some((types.next()?, types.next()?))
+}
+
+/// `PathBuf`, `OsString`, `&Path` or `&OsStr`, which C sees as strings in
+/// the GLib filename encoding
+pub(crate) fn is_filename_type(ty: &Type) -> bool {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    let ty = match ty {
+        Type::Reference(r) if r.mutability.is_none() => &*r.elem,
+        _ => ty,
+    };
+    matches!(
+        last_segment_ident(ty).as_deref(),
+        Some("PathBuf" | "OsString" | "Path" | "OsStr")
+    )
+}
+
+/// Ownership of a file descriptor passed as a `gint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FdOwnership {
+    /// `OwnedFd`, closed by whoever receives it
+    Owned,
+    /// `BorrowedFd` or `RawFd`, closed by whoever passes it
+    Borrowed,
+}
+
+/// Ownership of `OwnedFd`, `BorrowedFd` and `RawFd`, optionally wrapped in
+/// `Option` where -1 is `None`
+pub(crate) fn fd_ownership(ty: &Type) -> Option<FdOwnership> {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    match last_segment_ident(ty).as_deref() {
+        Some("OwnedFd") => Some(FdOwnership::Owned),
+        Some("BorrowedFd" | "RawFd") => Some(FdOwnership::Borrowed),
+        _ => None,
+    }
+}
+
+/// States who closes a file descriptor of type `ty`, which is handed to the
+/// callee for parameters and to the caller for out parameters and returns
+pub(crate) fn fd_ownership_doc(ty: &Type, to_callee: bool) -> Option<String> {
+    let doc = match (fd_ownership(ty)?, to_callee) {
+        (FdOwnership::Owned, true) => "a file descriptor closed by the callee",
+        (FdOwnership::Owned, false) => "a file descriptor the caller must close",
+        (FdOwnership::Borrowed, true) => "a file descriptor that stays owned by the caller",
+        (FdOwnership::Borrowed, false) => "a file descriptor the caller must not close",
+    };
+    Some(if extract_option_inner(ty).is_some() {
+        format!("{}, or -1", doc)
+    } else {
+        doc.to_string()
+    })
+}
+
+/// `Duration`, optionally wrapped in `Option`
+pub(crate) fn is_duration_type(ty: &Type) -> bool {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    last_segment_ident(ty).as_deref() == Some("Duration")
+}
+
+/// `Duration` or `SystemTime`, optionally wrapped in `Option`, which C sees
+/// as plain integers
+pub(crate) fn is_time_type(ty: &Type) -> bool {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    matches!(
+        last_segment_ident(ty).as_deref(),
+        Some("Duration" | "SystemTime")
+    )
+}
+
+/// States the unit of a duration or timestamp passed as an integer
+pub(crate) fn time_unit_doc(ty: &Type, unit: DurationUnit) -> Option<String> {
+    let inner = extract_option_inner(ty).unwrap_or(ty);
+    let (doc, none) = match (last_segment_ident(inner).as_deref()?, unit) {
+        ("Duration", DurationUnit::Millis) => ("in milliseconds", "%G_MAXUINT"),
+        ("Duration", DurationUnit::Micros) => ("in microseconds", "-1"),
+        ("SystemTime", _) => ("in microseconds since the Unix epoch", "%G_MININT64"),
+        _ => return None,
+    };
+    Some(if extract_option_inner(ty).is_some() {
+        format!("{}, or {} for none", doc, none)
+    } else {
+        doc.to_string()
+    })
+}
+
+/// Whether `type_string` is a single complete `GVariant` type string, as
+/// accepted by `g_variant_type_string_is_valid()`
+pub(crate) fn is_valid_variant_type(type_string: &str) -> bool {
+    let bytes = type_string.as_bytes();
+    variant_type_end(bytes, 0) == Some(bytes.len())
+}
+
+/// The static variant type string of `ty` for the std and GLib types whose
+/// `StaticVariantType` is known without running it, `None` for other types
+pub(crate) fn variant_type_string(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(variant_type_string)
+                .collect::<Option<String>>()?;
+            Some(format!("({})", elems))
+        }
+        Type::Reference(r) if r.mutability.is_none() => variant_type_string(&r.elem),
+        Type::Slice(slice) => Some(format!("a{}", variant_type_string(&slice.elem)?)),
+        Type::Path(_) => {
+            if let Some(inner) = extract_option_inner(ty) {
+                return Some(format!("m{}", variant_type_string(inner)?));
+            }
+            if let Some((key, value)) = extract_map_types(ty) {
+                return Some(format!(
+                    "a{{{}{}}}",
+                    variant_type_string(key)?,
+                    variant_type_string(value)?
+                ));
+            }
+            if let Some((elem, _)) = extract_array_elem(ty) {
+                return Some(format!("a{}", variant_type_string(elem)?));
+            }
+            let type_string = match last_segment_ident(ty)?.as_str() {
+                "bool" => "b",
+                "u8" => "y",
+                "i16" => "n",
+                "u16" => "q",
+                "i32" => "i",
+                "u32" => "u",
+                "i64" => "x",
+                "u64" => "t",
+                "f64" => "d",
+                "String" | "str" | "GString" | "GStr" => "s",
+                "ObjectPath" => "o",
+                "Signature" => "g",
+                "Variant" => "v",
+                "VariantDict" => "a{sv}",
+                _ => return None,
+            };
+            Some(type_string.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// End of the variant type starting at `start`, `None` if there is none
+fn variant_type_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match *bytes.get(start)? {
+        b'b' | b'y' | b'n' | b'q' | b'i' | b'u' | b'x' | b't' | b'h' | b'd' | b's' | b'o'
+        | b'g' | b'v' | b'*' | b'?' | b'r' => Some(start + 1),
+        b'a' | b'm' => variant_type_end(bytes, start + 1),
+        b'(' => {
+            let mut end = start + 1;
+            while *bytes.get(end)? != b')' {
+                end = variant_type_end(bytes, end)?;
+            }
+            Some(end + 1)
+        }
+        b'{' => {
+            let key = *bytes.get(start + 1)?;
+            if !b"bynqiuxthdsog?".contains(&key) {
+                return None;
+            }
+            let end = variant_type_end(bytes, start + 2)?;
+            (*bytes.get(end)? == b'}').then_some(end + 1)
+        }
+        _ => None,
+    }
+}
+
+/// GIR annotations describing what a pointer converted through `FfiConvert`
+/// holds, such as `(element-type utf8 utf8)` for maps or `(type filename)`
+/// for paths
+pub(crate) fn gir_type_annotations(ty: &Type) -> Vec<String> {
+    let ty = extract_option_inner(ty).unwrap_or(ty);
+    if is_filename_type(ty) {
+        vec!["type filename".to_string()]
+    } else if is_strv_type(ty) {
+        vec![
+            "array zero-terminated=1".to_string(),
+            "element-type utf8".to_string(),
+        ]
+    } else if let Some((key, value)) = extract_map_types(ty) {
+        vec![format!(
+            "element-type {} {}",
+            gir_element_type(key),
+            gir_element_type(value)
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+/// GIR name of an array element type, as used by `(element-type)`
+pub(crate) fn gir_element_type(ty: &Type) -> String {
+    let ty = match ty {
+        Type::Reference(r) if r.mutability.is_none() => &*r.elem,
+        _ => ty,
+    };
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            // Boxed GLib types go by their namespaced GIR names
+            match segment.ident.to_string().as_str() {
+                "String" | "str" | "GString" => return "utf8".to_string(),
+                "PathBuf" | "OsString" => return "filename".to_string(),
+                "Variant" => return "GLib.Variant".to_string(),
+                "Bytes" => return "GLib.Bytes".to_string(),
+                "DateTime" => return "GLib.DateTime".to_string(),
+                "HashMap" | "BTreeMap" => return "GLib.HashTable".to_string(),
+                "Value" => return "GObject.Value".to_string(),
+                "UnixFDList" => return "Gio.UnixFDList".to_string(),
+                _ => {}
+            }
+            if is_byte_vec(ty) {
+                return "GLib.Bytes".to_string();
+            }
+        }
+    }
+
+    rust_type_to_c_type_string(ty)
+        .trim_end_matches('*')
+        .to_string()
+}
+
 pub(crate) fn rust_type_to_c_type_string(ty: &Type) -> String {
     if let Some(inner) = extract_option_inner(ty) {
         return rust_type_to_c_type_string(inner);
@@ -144,11 +606,40 @@ pub(crate) fn rust_type_to_c_type_string(ty: &Type) -> String {
         return format!("{}*", rust_type_to_c_type_string(inner));
     }
 
+    if let Type::Reference(r) = ty {
+        return rust_type_to_c_type_string(&r.elem);
+    }
+
+    if is_strv_type(ty) {
+        return "gchar**".to_string();
+    }
+
+    // `Vec<u8>` outside of arrays is a `GBytes`, other vectors and slices
+    // are C arrays of their elements
+    if is_byte_vec(ty) {
+        return "GBytes*".to_string();
+    }
+    if let Some((elem, _)) = extract_array_elem(ty) {
+        return format!("{}*", rust_type_to_c_type_string(elem));
+    }
+    if let Type::Slice(slice) = ty {
+        return format!("{}*", rust_type_to_c_type_string(&slice.elem));
+    }
+
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
             let type_name = segment.ident.to_string();
             return match type_name.as_str() {
-                "String" | "str" => "gchar*".to_string(),
+                "String" | "str" | "GString" => "gchar*".to_string(),
+                "PathBuf" | "OsString" | "Path" | "OsStr" => "gchar*".to_string(),
+                "Bytes" => "GBytes*".to_string(),
+                "HashMap" | "BTreeMap" => "GHashTable*".to_string(),
+                "OwnedFd" | "BorrowedFd" | "RawFd" => "gint".to_string(),
+                "Duration" | "SystemTime" => "gint64".to_string(),
+                "DateTime" => "GDateTime*".to_string(),
+                "UnixFDList" => "GUnixFDList*".to_string(),
+                "Variant" => "GVariant*".to_string(),
+                "Value" => "GValue*".to_string(),
                 "bool" => "gboolean".to_string(),
                 "i8" => "gint8".to_string(),
                 "i16" => "gint16".to_string(),
@@ -0,0 +1,30 @@
+// Test that array options are rejected on non-array parameters
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    fn test_method(&self, #[ffi(transfer = container)] value: i32) -> i32 {
+        value
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Test that async methods cannot borrow closures
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    async fn test_method(&self, f: impl Fn(i32) -> i32) -> i32 {
+        f(1)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Test that async methods only update `&mut` numbers, in place
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    async fn test_method(&self, name: &mut String) {
+        *name = "async".to_string();
+    }
+}
+
+fn main() {}
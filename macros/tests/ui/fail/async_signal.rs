@@ -0,0 +1,31 @@
+// Test that signals cannot be emitted by async methods
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    #[ffi(signal)]
+    async fn test_method(&self, value: i32) -> i32 {
+        value
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Test that arrays of OwnedFd cannot be borrowed from C
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    fn test_method(&self, fds: Vec<std::os::fd::OwnedFd>) -> u32 {
+        fds.len() as u32
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// Test that methods can't take the names of the generated copy/free functions
+use gobject_macros::ffi_impl;
+
+#[derive(Clone, Debug, glib::Boxed)]
+#[boxed_type(name = "ClashRectangle")]
+pub struct Rectangle {
+    pub width: i32,
+}
+
+#[ffi_impl(prefix = "my", ty = "boxed")]
+impl Rectangle {
+    fn copy(&self) -> Rectangle {
+        self.clone()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Test that caller-allocated structs must be checked records
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+#[repr(C)]
+#[derive(Default)]
+pub struct Extents {
+    pub width: f64,
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    fn test_method(&self, #[ffi(caller_allocates)] extents: &mut Extents) {
+        extents.width = 1.0;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+// Test that error_value must be a value with a C spelling
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct TestObject;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestObject {
+        const NAME: &'static str = "TestObject";
+        type Type = super::TestObject;
+    }
+
+    impl ObjectImpl for TestObject {}
+}
+
+glib::wrapper! {
+    pub struct TestObject(ObjectSubclass<imp::TestObject>);
+}
+
+#[ffi_impl]
+impl TestObject {
+    #[ffi(error_value = i32::MIN + 1)]
+    fn test_method(&self) -> Result<i32, glib::Error> {
+        Ok(0)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Test that the declared parent must match ObjectSubclass::ParentType
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Widget;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Widget {
+        const NAME: &'static str = "ParentMismatchWidget";
+        type Type = super::Widget;
+    }
+
+    impl ObjectImpl for Widget {}
+}
+
+glib::wrapper! {
+    pub struct Widget(ObjectSubclass<imp::Widget>);
+}
+
+#[ffi_impl(parent = gio::Application, implements(gio::ListModel))]
+impl Widget {
+    fn is_visible(&self) -> bool {
+        true
+    }
+}
+
+fn main() {}
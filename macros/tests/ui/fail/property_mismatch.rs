@@ -0,0 +1,54 @@
+// Test that declared properties must match the derived ones
+use std::cell::{Cell, RefCell};
+
+use glib::prelude::*;
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::Thermostat)]
+    pub struct Thermostat {
+        #[property(get, set, minimum = -50, maximum = 50)]
+        target: Cell<i32>,
+        #[property(get, set, nullable)]
+        label: RefCell<Option<String>>,
+        #[property(get, set, construct_only)]
+        serial: Cell<u64>,
+        #[property(get, name = "is-heating")]
+        heating: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Thermostat {
+        const NAME: &'static str = "PropertyThermostat";
+        type Type = super::Thermostat;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for Thermostat {}
+}
+
+glib::wrapper! {
+    pub struct Thermostat(ObjectSubclass<imp::Thermostat>);
+}
+
+#[ffi_impl(properties(
+    #[property(get, set)] target: i64,
+    #[property(get, set, nullable)] label: Option<String>,
+    #[property(get, set, construct_only)] serial: u64,
+    #[property(get, set, name = "is-heating")] heating: bool,
+))]
+impl Thermostat {
+    fn new(serial: u64) -> Thermostat {
+        glib::Object::builder().property("serial", serial).build()
+    }
+
+    fn warm_up(&self) {
+        self.set_target(self.target() + 1);
+    }
+}
+
+fn main() {}
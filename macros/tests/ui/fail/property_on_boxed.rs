@@ -0,0 +1,17 @@
+// Test that properties are rejected on non-GObject types
+use gobject_macros::ffi_impl;
+
+#[derive(Clone, Debug, glib::Boxed)]
+#[boxed_type(name = "PropertyPoint")]
+pub struct Point {
+    pub x: i32,
+}
+
+#[ffi_impl(ty = "boxed", properties(#[property(get)] x: i32))]
+impl Point {
+    fn x(&self) -> i32 {
+        self.x
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// Test that async methods are rejected on records
+use gobject_macros::ffi_impl;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[ffi_impl(ty = "struct", fields(start: i64, end: i64))]
+impl Range {
+    async fn len(&self) -> Result<i64, glib::Error> {
+        Ok(self.end - self.start)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Test that a record whose Rust layout differs from its C fields is rejected
+use gobject_macros::ffi_impl;
+
+// `bool` is one byte, but the C side sees a four-byte `gboolean`
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Toggle {
+    pub id: u32,
+    pub enabled: bool,
+}
+
+#[ffi_impl(ty = "struct", fields(id: u32, enabled: bool))]
+impl Toggle {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Test that a record must declare every Rust field, even one in padding
+use gobject_macros::ffi_impl;
+
+// `flags` fits in the tail padding after `id`
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Sample {
+    pub value: f64,
+    pub id: u32,
+    pub flags: u32,
+}
+
+#[ffi_impl(ty = "struct", fields(value: f64, id: u32))]
+impl Sample {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Test that records can't keep returned `transfer none` arrays alive
+use gobject_macros::ffi_impl;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[ffi_impl(ty = "struct", fields(start: i64, end: i64))]
+impl Range {
+    #[ffi(transfer = none)]
+    fn values(&self) -> Vec<i64> {
+        (self.start..self.end).collect()
+    }
+}
+
+fn main() {}
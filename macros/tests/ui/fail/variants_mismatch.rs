@@ -0,0 +1,19 @@
+// Test that declared variants must match the enum
+use gobject_macros::ffi_impl;
+
+#[derive(Clone, Debug, PartialEq, glib::Boxed)]
+#[boxed_type(name = "MyShape")]
+pub enum Shape {
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+    Empty,
+}
+
+#[ffi_impl(
+    prefix = "my",
+    ty = "boxed",
+    variants(Circle(f32), Rect { width: f64, height: f64 }, Empty)
+)]
+impl Shape {}
+
+fn main() {}
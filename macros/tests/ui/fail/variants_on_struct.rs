@@ -0,0 +1,18 @@
+// Test that tagged union variants are rejected on non-boxed types
+use gobject_macros::ffi_impl;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Size {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[ffi_impl(ty = "struct", fields(width: u32, height: u32), variants(Square(u32)))]
+impl Size {
+    fn area(&self) -> u32 {
+        self.width * self.height
+    }
+}
+
+fn main() {}
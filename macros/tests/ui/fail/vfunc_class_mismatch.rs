@@ -0,0 +1,31 @@
+// Test that the subclass must use the generated class struct
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Shape;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Shape {
+        const NAME: &'static str = "VfuncMismatchShape";
+        type Type = super::Shape;
+    }
+
+    impl ObjectImpl for Shape {}
+}
+
+glib::wrapper! {
+    pub struct Shape(ObjectSubclass<imp::Shape>);
+}
+
+#[ffi_impl]
+impl Shape {
+    #[ffi(vfunc)]
+    fn area(&self) -> f64 {
+        0.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+// Test that constructors can't be virtual methods
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Shape;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Shape {
+        const NAME: &'static str = "VfuncConstructorShape";
+        type Type = super::Shape;
+    }
+
+    impl ObjectImpl for Shape {}
+}
+
+glib::wrapper! {
+    pub struct Shape(ObjectSubclass<imp::Shape>);
+}
+
+#[ffi_impl]
+impl Shape {
+    #[ffi(vfunc)]
+    fn new() -> Shape {
+        glib::Object::new()
+    }
+}
+
+fn main() {}
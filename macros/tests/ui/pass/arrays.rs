@@ -0,0 +1,123 @@
+// Test slices and Vec<T> marshalled as C arrays with explicit lengths
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Statistics;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Statistics {
+        const NAME: &'static str = "ArrayStatistics";
+        type Type = super::Statistics;
+    }
+
+    impl ObjectImpl for Statistics {}
+}
+
+glib::wrapper! {
+    pub struct Statistics(ObjectSubclass<imp::Statistics>);
+}
+
+#[ffi_impl]
+impl Statistics {
+    // (array length=n_values) (element-type gint32) (transfer none)
+    fn sum(&self, values: &[i32]) -> i64 {
+        values.iter().map(|&v| v as i64).sum()
+    }
+
+    fn mean(&self, samples: Vec<f64>) -> f64 {
+        samples.iter().sum::<f64>() / samples.len().max(1) as f64
+    }
+
+    // Elements can be any FfiConvert type
+    fn join(&self, words: &[String], separator: String) -> String {
+        words.join(&separator)
+    }
+
+    // (element-type GLib.Variant)
+    fn count_variants(&self, values: &[glib::Variant]) -> u32 {
+        values.len() as u32
+    }
+
+    fn count_labels(&self, labels: Option<&[String]>) -> u32 {
+        labels.map_or(0, |labels| labels.len() as u32)
+    }
+
+    // The array storage, or the storage and the elements, are taken over
+    fn consume(&self, #[ffi(transfer = container)] words: Vec<String>) -> u32 {
+        words.len() as u32
+    }
+
+    fn consume_all(&self, #[ffi(transfer = full)] words: Vec<String>) -> u32 {
+        words.len() as u32
+    }
+
+    // (array zero-terminated=1): no length parameter
+    fn count_words(&self, #[ffi(zero_terminated)] words: &[String]) -> u32 {
+        words.len() as u32
+    }
+
+    // Returns: (array length=out_length) (element-type gdouble) (transfer full)
+    fn squares(&self, count: u32) -> Vec<f64> {
+        (0..count).map(|i| (i * i) as f64).collect()
+    }
+
+    fn labels(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    #[ffi(zero_terminated)]
+    fn words(&self) -> Vec<String> {
+        vec!["a".to_string(), "b".to_string()]
+    }
+
+    fn try_squares(&self, count: u32) -> Result<Vec<u64>, glib::Error> {
+        Ok((0..count as u64).map(|i| i * i).collect())
+    }
+
+    // (transfer none): the array stays owned by the object until the next
+    // call
+    #[ffi(transfer = none)]
+    fn cached_squares(&self, count: u32) -> Vec<f64> {
+        (0..count).map(|i| (i * i) as f64).collect()
+    }
+
+    // (transfer container): the caller frees the array, not the strings
+    #[ffi(transfer = container)]
+    fn cached_words(&self) -> Option<Vec<String>> {
+        Some(vec!["a".to_string(), "b".to_string()])
+    }
+
+    #[ffi(transfer = none, collection = list)]
+    fn cached_list(&self) -> Vec<String> {
+        vec!["a".to_string()]
+    }
+
+    #[ffi(transfer = container, collection = ptr_array)]
+    fn cached_ptr_array(&self) -> Vec<String> {
+        vec!["a".to_string()]
+    }
+
+    // (out) (array length=n_values): out arrays are transfer full
+    fn fill(&self, values: &mut Vec<i32>) {
+        values.extend([1, 2, 3]);
+    }
+
+    fn double_all(&self, #[ffi(inout)] values: &mut Vec<i32>) {
+        values.iter_mut().for_each(|v| *v *= 2);
+    }
+
+    fn maybe_fill(&self, values: Option<&mut Vec<String>>) {
+        if let Some(values) = values {
+            values.push("value".to_string());
+        }
+    }
+
+    async fn fetch_bytes(&self, request: Vec<u8>) -> Result<Vec<u8>, glib::Error> {
+        Ok(request)
+    }
+}
+
+fn main() {}
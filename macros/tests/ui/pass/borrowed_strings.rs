@@ -0,0 +1,57 @@
+// Test &str, &GStr and &CStr parameters borrowed from const char*
+use std::ffi::CStr;
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Greeter;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Greeter {
+        const NAME: &'static str = "BorrowedStringGreeter";
+        type Type = super::Greeter;
+    }
+
+    impl ObjectImpl for Greeter {}
+}
+
+glib::wrapper! {
+    pub struct Greeter(ObjectSubclass<imp::Greeter>);
+}
+
+#[ffi_impl]
+impl Greeter {
+    // Invalid UTF-8 is logged as a critical and 0 is returned
+    fn name_len(&self, name: &str) -> u32 {
+        name.len() as u32
+    }
+
+    fn greet(&self, greeting: &glib::GStr, name: Option<&str>) -> String {
+        format!("{} {}", greeting, name.unwrap_or("world"))
+    }
+
+    // No UTF-8 validation
+    fn byte_len(&self, data: &CStr, suffix: Option<&CStr>) -> u64 {
+        (data.to_bytes().len() + suffix.map_or(0, |suffix| suffix.to_bytes().len())) as u64
+    }
+
+    // Invalid UTF-8 is reported through the GError
+    fn parse(&self, text: &str) -> Result<i32, glib::Error> {
+        text.parse()
+            .map_err(|_| glib::Error::new(glib::ConvertError::Failed, "not a number"))
+    }
+
+    fn log(&self, message: &str) {
+        let _ = message;
+    }
+
+    // Async methods copy the strings before the call is scheduled
+    async fn shout(&self, text: &str, suffix: Option<&glib::GStr>) -> Result<String, glib::Error> {
+        Ok(format!("{}{}", text.to_uppercase(), suffix.map_or("", |s| s.as_str())))
+    }
+}
+
+fn main() {}
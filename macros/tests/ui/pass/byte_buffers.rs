@@ -0,0 +1,71 @@
+// Test byte buffers passed and returned without copying
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Image;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Image {
+        const NAME: &'static str = "ByteBufferImage";
+        type Type = super::Image;
+    }
+
+    impl ObjectImpl for Image {}
+}
+
+glib::wrapper! {
+    pub struct Image(ObjectSubclass<imp::Image>);
+}
+
+#[ffi_impl]
+impl Image {
+    // const guint8* + gsize, borrowed for the call
+    fn checksum(&self, data: &[u8]) -> u32 {
+        data.iter().map(|&b| b as u32).sum()
+    }
+
+    fn checksum_or_zero(&self, data: Option<&[u8]>) -> u32 {
+        data.map_or(0, |data| data.iter().map(|&b| b as u32).sum())
+    }
+
+    fn mean(&self, samples: &[f64]) -> f64 {
+        samples.iter().sum::<f64>() / samples.len().max(1) as f64
+    }
+
+    // The GBytes is borrowed, not copied
+    fn size(&self, bytes: &glib::Bytes) -> u64 {
+        bytes.len() as u64
+    }
+
+    fn size_or_zero(&self, bytes: Option<&glib::Bytes>) -> u64 {
+        bytes.map_or(0, |bytes| bytes.len() as u64)
+    }
+
+    // The Vec is wrapped in the returned GBytes without copying
+    fn encode(&self, width: u32) -> glib::Bytes {
+        glib::Bytes::from_owned(vec![0u8; width as usize])
+    }
+
+    // Returned as a GBytes owning the Vec, not a copied guint8* array
+    fn render(&self, width: u32) -> Vec<u8> {
+        vec![255; width as usize]
+    }
+
+    fn render_checked(&self, width: u32) -> Result<Option<Vec<u8>>, glib::Error> {
+        Ok((width > 0).then(|| vec![255; width as usize]))
+    }
+
+    fn slice(&self, bytes: &glib::Bytes, offset: u64) -> Option<glib::Bytes> {
+        (offset as usize <= bytes.len()).then(|| glib::Bytes::from_bytes(bytes, offset as usize..))
+    }
+
+    // Async methods copy the buffer before the call is scheduled
+    async fn upload(&self, data: &[u8], bytes: &glib::Bytes) -> Result<u64, glib::Error> {
+        Ok((data.len() + bytes.len()) as u64)
+    }
+}
+
+fn main() {}
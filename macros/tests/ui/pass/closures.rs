@@ -0,0 +1,92 @@
+// Test closure parameters passed as C callbacks with user_data
+use std::cell::RefCell;
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct List {
+        pub items: RefCell<Vec<String>>,
+        pub filter: RefCell<Option<Box<dyn Fn(&str) -> bool>>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for List {
+        const NAME: &'static str = "ClosureList";
+        type Type = super::List;
+    }
+
+    impl ObjectImpl for List {}
+}
+
+glib::wrapper! {
+    pub struct List(ObjectSubclass<imp::List>);
+}
+
+#[ffi_impl]
+impl List {
+    // (scope call): only called during the call
+    fn foreach(&self, f: impl Fn(&str) -> bool) -> u32 {
+        let mut count = 0;
+        for item in self.imp().items.borrow().iter() {
+            count += 1;
+            if !f(item) {
+                break;
+            }
+        }
+        count
+    }
+
+    fn sum(&self, values: &[i32], map: &dyn Fn(i32) -> i64) -> i64 {
+        values.iter().map(|&value| map(value)).sum()
+    }
+
+    fn visit(&self, visitor: Option<&mut dyn FnMut(u32)>) {
+        if let Some(visitor) = visitor {
+            for index in 0..self.imp().items.borrow().len() {
+                visitor(index as u32);
+            }
+        }
+    }
+
+    // (scope notified): kept until the GDestroyNotify is called
+    fn set_filter(&self, filter: Option<Box<dyn Fn(&str) -> bool>>) {
+        self.imp().filter.replace(filter);
+    }
+
+    fn set_sort_func(&self, compare: impl Fn(&str, &str) -> i32 + 'static) {
+        self.imp()
+            .items
+            .borrow_mut()
+            .sort_by(|a, b| compare(a, b).cmp(&0));
+    }
+
+    // (scope async): called once, later
+    fn when_done(&self, done: impl FnOnce(u32) + 'static) {
+        done(self.imp().items.borrow().len() as u32);
+    }
+
+    // Several closures get typedefs named after the parameter too
+    fn partition(
+        &self,
+        keep: impl Fn(&str) -> bool,
+        #[ffi(thread_safe)] on_dropped: Box<dyn Fn(String) + Send + Sync>,
+    ) {
+        self.imp().items.borrow_mut().retain(|item| {
+            let keep = keep(item);
+            if !keep {
+                on_dropped(item.clone());
+            }
+            keep
+        });
+    }
+
+    async fn load(&self, progress: Box<dyn Fn(f64)>) -> u32 {
+        progress(1.0);
+        0
+    }
+}
+
+fn main() {}
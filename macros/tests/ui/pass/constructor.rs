@@ -4,8 +4,11 @@ use gobject_macros::ffi_impl;
 
 mod imp {
     use super::*;
+    // The `RefCell` keeps `Widget` from being `Send`
     #[derive(Default)]
-    pub struct Widget;
+    pub struct Widget {
+        pub(super) name: std::cell::RefCell<String>,
+    }
 
     #[glib::object_subclass]
     impl ObjectSubclass for Widget {
@@ -28,8 +31,9 @@ impl Widget {
     }
 
     fn with_name(name: String) -> Widget {
-        let _ = name;
-        glib::Object::new::<Widget>()
+        let widget = glib::Object::new::<Widget>();
+        widget.imp().name.replace(name);
+        widget
     }
 
     fn try_new() -> Result<Widget, glib::Error> {
@@ -42,8 +46,11 @@ impl Widget {
     }
 
     async fn try_new_async(name: String) -> Result<Widget, glib::Error> {
-        let _ = name;
-        Ok(glib::Object::new::<Widget>())
+        Ok(Widget::with_name(name))
+    }
+
+    async fn rename(&self, name: String) -> String {
+        self.imp().name.replace(name)
     }
 }
 
@@ -0,0 +1,66 @@
+// Test per-method error return values
+use glib::subclass::prelude::*;
+use gobject_macros::{c_return_type, ffi_impl};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, glib::Enum)]
+#[enum_type(name = "ErrorValueStatus")]
+enum Status {
+    Idle,
+    Running,
+}
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct ErrorValues;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ErrorValues {
+        const NAME: &'static str = "ErrorValues";
+        type Type = super::ErrorValues;
+    }
+
+    impl ObjectImpl for ErrorValues {}
+}
+
+glib::wrapper! {
+    pub struct ErrorValues(ObjectSubclass<imp::ErrorValues>);
+}
+
+#[ffi_impl]
+impl ErrorValues {
+    // -1 is a valid result, callers must check the GError
+    #[ffi(error_value = 0)]
+    fn offset(&self, value: i32) -> Result<i32, glib::Error> {
+        Ok(value - 1)
+    }
+
+    #[ffi(error_value = u32::MAX)]
+    fn find(&self, needle: u32) -> Result<u32, glib::Error> {
+        Ok(needle)
+    }
+
+    #[ffi(error_value = f64::NAN)]
+    async fn measure(&self) -> Result<f64, glib::Error> {
+        Ok(1.0)
+    }
+
+    // Also used when the async operation is cancelled
+    #[ffi(error_value = -1)]
+    async fn count(&self) -> i64 {
+        42
+    }
+
+    #[ffi(error_value = glib::ffi::GTRUE)]
+    fn is_empty(&self) -> Result<bool, glib::Error> {
+        Ok(false)
+    }
+
+    #[c_return_type(i32, transfer = primitive)]
+    #[ffi(error_value = -1)]
+    fn status(&self) -> Result<Status, glib::Error> {
+        Ok(Status::Idle)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,81 @@
+// Test file descriptors passed with their ownership
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Channel {
+        pub(super) fd: std::cell::RefCell<Option<OwnedFd>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Channel {
+        const NAME: &'static str = "FdChannel";
+        type Type = super::Channel;
+    }
+
+    impl ObjectImpl for Channel {}
+}
+
+glib::wrapper! {
+    pub struct Channel(ObjectSubclass<imp::Channel>);
+}
+
+#[ffi_impl]
+impl Channel {
+    // (transfer full) gint, closed by the callee
+    fn attach(&self, fd: OwnedFd) {
+        self.imp().fd.replace(Some(fd));
+    }
+
+    // -1 detaches
+    fn replace(&self, fd: Option<OwnedFd>) -> Option<OwnedFd> {
+        self.imp().fd.replace(fd)
+    }
+
+    // (transfer none) gint, still owned by the caller
+    fn write_to(&self, fd: BorrowedFd<'_>, raw: RawFd) -> bool {
+        fd.as_raw_fd() >= 0 && raw >= 0
+    }
+
+    fn duplicate(&self) -> Result<OwnedFd, glib::Error> {
+        self.imp()
+            .fd
+            .borrow()
+            .as_ref()
+            .ok_or_else(|| glib::Error::new(glib::FileError::Badf, "Not attached"))?
+            .try_clone()
+            .map_err(|e| glib::Error::new(glib::FileError::Failed, &e.to_string()))
+    }
+
+    fn raw(&self) -> RawFd {
+        self.imp().fd.borrow().as_ref().map_or(-1, |fd| fd.as_fd().as_raw_fd())
+    }
+
+    fn take(&self, fd: &mut Option<OwnedFd>) {
+        *fd = self.imp().fd.take();
+    }
+
+    // Arrays of descriptors are taken over along with the array
+    fn attach_all(&self, #[ffi(transfer = full)] fds: Vec<OwnedFd>) -> u32 {
+        fds.len() as u32
+    }
+
+    fn send(&self, fds: &gio::UnixFDList) -> i32 {
+        gio::prelude::UnixFDListExt::length(fds)
+    }
+
+    fn fd_list(&self) -> gio::UnixFDList {
+        gio::UnixFDList::new()
+    }
+
+    async fn attach_async(&self, fd: OwnedFd) -> Result<(), glib::Error> {
+        self.attach(fd);
+        Ok(())
+    }
+}
+
+fn main() {}
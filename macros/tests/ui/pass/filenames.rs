@@ -0,0 +1,55 @@
+// Test paths and OS strings passed in the GLib filename encoding
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Loader;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Loader {
+        const NAME: &'static str = "FilenameLoader";
+        type Type = super::Loader;
+    }
+
+    impl ObjectImpl for Loader {}
+}
+
+glib::wrapper! {
+    pub struct Loader(ObjectSubclass<imp::Loader>);
+}
+
+#[ffi_impl]
+impl Loader {
+    // (type filename) const char*, borrowed byte for byte
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn join(&self, dir: PathBuf, name: &OsStr) -> PathBuf {
+        dir.join(name)
+    }
+
+    fn extension(&self, path: Option<&Path>) -> Option<OsString> {
+        path.and_then(Path::extension).map(OsStr::to_os_string)
+    }
+
+    fn resolve(&self, name: OsString, resolved: &mut PathBuf) -> bool {
+        *resolved = PathBuf::from(name);
+        true
+    }
+
+    fn search_path(&self) -> Vec<PathBuf> {
+        vec![PathBuf::from("/usr/share")]
+    }
+
+    async fn load(&self, path: &Path) -> Result<u64, glib::Error> {
+        Ok(path.as_os_str().len() as u64)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,66 @@
+// Test HashMap and BTreeMap marshalled as GHashTable
+use std::collections::{BTreeMap, HashMap};
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Config;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Config {
+        const NAME: &'static str = "HashTableConfig";
+        type Type = super::Config;
+    }
+
+    impl ObjectImpl for Config {}
+}
+
+glib::wrapper! {
+    pub struct Config(ObjectSubclass<imp::Config>);
+}
+
+#[ffi_impl]
+impl Config {
+    // (element-type utf8 utf8) (transfer none) GHashTable*
+    fn apply(&self, settings: HashMap<String, String>) -> u32 {
+        settings.len() as u32
+    }
+
+    // The table reference is taken over
+    fn apply_owned(
+        &self,
+        #[ffi(transfer = full)] settings: HashMap<String, glib::Variant>,
+    ) -> u32 {
+        settings.len() as u32
+    }
+
+    fn apply_sorted(&self, settings: Option<BTreeMap<String, i32>>) -> u32 {
+        settings.map_or(0, |settings| settings.len() as u32)
+    }
+
+    // Returns: (element-type utf8 utf8) (transfer full)
+    fn settings(&self) -> HashMap<String, String> {
+        HashMap::from([("key".to_string(), "value".to_string())])
+    }
+
+    fn counts(&self) -> BTreeMap<u32, bool> {
+        BTreeMap::from([(1, true)])
+    }
+
+    fn variants(&self) -> Result<HashMap<String, glib::Variant>, glib::Error> {
+        Ok(HashMap::new())
+    }
+
+    fn fill(&self, settings: &mut HashMap<String, String>) {
+        settings.insert("key".to_string(), "value".to_string());
+    }
+
+    async fn fetch(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+// Test FFI generation for interfaces declared in Rust
+use glib::prelude::*;
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+
+    #[glib::object_interface]
+    impl ObjectInterface for Named {
+        const NAME: &'static str = "InterfaceNamed";
+        // Generated by ffi_impl from the `vfunc` methods
+        type Interface = super::ffi::NamedInterface;
+    }
+
+    pub enum Named {}
+}
+
+glib::wrapper! {
+    pub struct Named(ObjectInterface<imp::Named>);
+}
+
+#[ffi_impl(ty = "interface")]
+impl Named {
+    #[ffi(vfunc)]
+    fn name(&self) -> String {
+        String::from("unnamed")
+    }
+
+    #[ffi(vfunc)]
+    fn rename(&self, name: &str) -> Result<(), glib::Error> {
+        let _ = name;
+        Err(glib::Error::new(glib::FileError::Perm, "read-only"))
+    }
+
+    // Not overridable
+    fn greeting(&self) -> String {
+        format!("Hello, {}", self.name())
+    }
+
+    fn type_name(&self) -> String {
+        self.type_().name().to_string()
+    }
+}
+
+fn main() {
+    let named = gobject_ffi::manifest::TypeManifest::of::<Named>();
+    assert!(named.is_interface);
+    assert_eq!(named.parent, None);
+    assert!(named.to_gir().starts_with("<interface name=\"Named\" c:type=\"Named\""));
+}
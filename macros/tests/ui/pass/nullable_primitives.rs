@@ -0,0 +1,83 @@
+// Test Option<primitive> parameters as nullable pointers and returns as out
+// parameters, with the sentinel behaviour available on request
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct NullableHandler;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for NullableHandler {
+        const NAME: &'static str = "NullableHandler";
+        type Type = super::NullableHandler;
+    }
+
+    impl ObjectImpl for NullableHandler {}
+}
+
+glib::wrapper! {
+    pub struct NullableHandler(ObjectSubclass<imp::NullableHandler>);
+}
+
+#[ffi_impl]
+impl NullableHandler {
+    // Nullable pointer parameters
+    fn add_optional(&self, a: i32, b: Option<i32>) -> i32 {
+        a + b.unwrap_or(0)
+    }
+
+    fn scale(&self, value: f64, factor: Option<f64>, round: Option<bool>) -> f64 {
+        let scaled = value * factor.unwrap_or(1.0);
+        if round.unwrap_or(false) { scaled.round() } else { scaled }
+    }
+
+    // Out parameter plus gboolean returns
+    fn checked_div(&self, a: i32, b: i32) -> Option<i32> {
+        a.checked_div(b)
+    }
+
+    fn try_parse(&self, text: String) -> Result<Option<u64>, glib::Error> {
+        Ok(text.parse().ok())
+    }
+
+    async fn find_index(&self, needle: Option<u32>) -> Option<u32> {
+        needle
+    }
+
+    async fn try_find_index(&self, needle: u32) -> Result<Option<i64>, glib::Error> {
+        Ok(Some(needle as i64))
+    }
+
+    // Explicit sentinel behaviour
+    fn add_sentinel(&self, a: i32, #[ffi(sentinel)] b: Option<i32>) -> i32 {
+        a + b.unwrap_or(0)
+    }
+
+    #[ffi(sentinel)]
+    fn first_positive(&self, a: i32, b: i32) -> Option<u32> {
+        [a, b].into_iter().find(|v| *v > 0).map(|v| v as u32)
+    }
+
+    // Only the return value uses the sentinel, `fallback` stays a nullable
+    // pointer
+    #[ffi(sentinel)]
+    fn first_or(&self, values: Vec<u32>, fallback: Option<u32>) -> Option<u32> {
+        values.first().copied().or(fallback)
+    }
+}
+
+fn main() {
+    let handler = glib::Object::new::<NullableHandler>();
+    let ptr = glib::object::ObjectType::as_ptr(&handler) as *mut _;
+    let values = [7u32];
+    let fallback = 3u32;
+    unsafe {
+        assert_eq!(ffi::nullable_handler_first_positive(ptr, -1, 4), 4);
+        assert_eq!(ffi::nullable_handler_first_positive(ptr, -1, -2), 0);
+        assert_eq!(ffi::nullable_handler_first_or(ptr, values.as_ptr(), 1, &fallback), 7);
+        assert_eq!(ffi::nullable_handler_first_or(ptr, values.as_ptr(), 0, &fallback), 3);
+        assert_eq!(ffi::nullable_handler_first_or(ptr, values.as_ptr(), 0, std::ptr::null()), 0);
+    }
+}
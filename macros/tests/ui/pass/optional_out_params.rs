@@ -0,0 +1,84 @@
+// Test optional out parameters: `Option<&mut T>` is `None` when C passes NULL
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod extents {
+    use gobject_macros::ffi_impl;
+
+    // Caller-allocated structs are records whose layout C knows
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub struct Extents {
+        pub width: f64,
+        pub height: f64,
+    }
+
+    #[ffi_impl(ty = "struct", fields(width: f64, height: f64))]
+    impl Extents {}
+}
+
+use extents::Extents;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Parser;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Parser {
+        const NAME: &'static str = "OptionalOutParser";
+        type Type = super::Parser;
+    }
+
+    impl ObjectImpl for Parser {}
+}
+
+glib::wrapper! {
+    pub struct Parser(ObjectSubclass<imp::Parser>);
+}
+
+#[ffi_impl]
+impl Parser {
+    // (out) (optional): the conversion is skipped when the caller passes NULL
+    fn parse(&self, input: i32, error_message: Option<&mut String>) -> bool {
+        if let Some(message) = error_message {
+            *message = format!("cannot parse {input}");
+        }
+        false
+    }
+
+    fn count(&self, #[ffi(out)] total: Option<&mut u32>, #[ffi(out)] valid: Option<&mut bool>) {
+        if let Some(total) = total {
+            *total = 3;
+        }
+        if let Some(valid) = valid {
+            *valid = true;
+        }
+    }
+
+    // Plain `&mut T` out parameters also accept NULL
+    fn position(
+        &self,
+        #[ffi(out)] x: &mut i32,
+        #[ffi(out)] y: &mut i32,
+        #[ffi(out)] visible: &mut bool,
+    ) {
+        *x = 1;
+        *y = 2;
+        *visible = true;
+    }
+
+    fn annotate(&self, #[ffi(inout)] text: Option<&mut String>) {
+        if let Some(text) = text {
+            text.push('!');
+        }
+    }
+
+    fn extents(&self, #[ffi(caller_allocates)] extents: Option<&mut Extents>) {
+        if let Some(extents) = extents {
+            extents.width = 1.0;
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,97 @@
+// Test out, inout and caller-allocates marshalling of `&mut T` parameters
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod extents {
+    use gobject_macros::ffi_impl;
+
+    // Caller-allocated structs are records whose layout C knows
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub struct Extents {
+        pub width: f64,
+        pub height: f64,
+    }
+
+    #[ffi_impl(ty = "struct", fields(width: f64, height: f64))]
+    impl Extents {}
+}
+
+use extents::Extents;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct OutHandler;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for OutHandler {
+        const NAME: &'static str = "OutHandler";
+        type Type = super::OutHandler;
+    }
+
+    impl ObjectImpl for OutHandler {}
+}
+
+glib::wrapper! {
+    pub struct OutHandler(ObjectSubclass<imp::OutHandler>);
+}
+
+#[ffi_impl]
+impl OutHandler {
+    // (out) (transfer full): Rust fills a default value that is written back
+    fn get_name(&self, name: &mut String) {
+        *name = "handler".to_string();
+    }
+
+    fn read_data(&self, data: &mut Vec<u8>, path: &mut std::path::PathBuf) -> bool {
+        data.extend_from_slice(b"data");
+        *path = std::path::PathBuf::from("/tmp/data");
+        true
+    }
+
+    fn maybe_label(&self, label: &mut Option<String>) {
+        *label = Some("label".to_string());
+    }
+
+    // Out parameters are only written on success
+    fn try_load(&self, contents: &mut String) -> Result<(), glib::Error> {
+        *contents = "contents".to_string();
+        Ok(())
+    }
+
+    // (inout) (transfer full): the caller's value is taken over
+    fn append_suffix(&self, #[ffi(inout)] text: &mut String) {
+        text.push_str("-suffix");
+    }
+
+    fn bump(&self, #[ffi(inout)] value: &mut i32) {
+        *value += 1;
+    }
+
+    // Numbers are (inout) unless marked `out`
+    fn add_to(&self, total: &mut i32, value: i32) {
+        *total += value;
+    }
+
+    fn reset(&self, #[ffi(out)] count: &mut u32) {
+        *count += 1;
+    }
+
+    // (out caller-allocates): the caller provides the struct storage
+    fn get_extents(&self, #[ffi(caller_allocates)] extents: &mut Extents) {
+        extents.width = 10.0;
+        extents.height = 20.0;
+    }
+}
+
+fn main() {
+    let handler: OutHandler = glib::Object::new();
+    let handler_ptr = glib::object::ObjectType::as_ptr(&handler) as *mut _;
+    let mut total = 40;
+    unsafe { ffi::out_handler_add_to(handler_ptr, &mut total, 2) };
+    assert_eq!(total, 42);
+    let mut count = 7;
+    unsafe { ffi::out_handler_reset(handler_ptr, &mut count) };
+    assert_eq!(count, 1);
+}
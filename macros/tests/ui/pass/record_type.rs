@@ -0,0 +1,62 @@
+// Test FFI generation for plain C struct records
+use gobject_macros::ffi_impl;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub id: u32,
+}
+
+#[ffi_impl(
+    prefix = "my",
+    ty = "struct",
+    fields(
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+        id: u32,
+    )
+)]
+impl Point {
+    // (out caller-allocates): the record is written to `out_result`
+    fn new(x: f64, y: f64) -> Point {
+        Point { x, y, id: 0 }
+    }
+
+    fn length(&self) -> f64 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    fn translate(&mut self, dx: f64, dy: f64) {
+        self.x += dx;
+        self.y += dy;
+    }
+
+    fn midpoint(&self, other: &Point) -> Self {
+        Point {
+            x: (self.x + other.x) / 2.0,
+            y: (self.y + other.y) / 2.0,
+            id: self.id,
+        }
+    }
+
+    fn normalized(&self) -> Result<Point, glib::Error> {
+        let length = self.length();
+        if length == 0.0 {
+            return Err(glib::Error::new(glib::FileError::Inval, "zero length"));
+        }
+        Ok(Point {
+            x: self.x / length,
+            y: self.y / length,
+            id: self.id,
+        })
+    }
+}
+
+fn main() {
+    #[allow(unused_imports)]
+    use ffi::MyPoint;
+}
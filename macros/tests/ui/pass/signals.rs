@@ -0,0 +1,61 @@
+// Test connect_ helpers for signals emitted by Rust methods
+use std::sync::OnceLock;
+
+use glib::prelude::*;
+use glib::subclass::{Signal, prelude::*};
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Counter;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Counter {
+        const NAME: &'static str = "SignalCounter";
+        type Type = super::Counter;
+    }
+
+    impl ObjectImpl for Counter {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| {
+                vec![
+                    Signal::builder("value-changed")
+                        .param_types([i32::static_type(), i32::static_type()])
+                        .build(),
+                    Signal::builder("should-reset")
+                        .param_types([String::static_type()])
+                        .return_type::<bool>()
+                        .build(),
+                    Signal::builder("reset").build(),
+                ]
+            })
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct Counter(ObjectSubclass<imp::Counter>);
+}
+
+#[ffi_impl]
+impl Counter {
+    // The signal is named after the emitting method
+    #[ffi(signal)]
+    fn value_changed(&self, old: i32, new: i32) {
+        self.emit_by_name::<()>("value-changed", &[&old, &new]);
+    }
+
+    #[ffi(signal = "should-reset")]
+    fn ask_reset(&self, reason: &str) -> bool {
+        self.emit_by_name::<bool>("should-reset", &[&reason])
+    }
+
+    #[ffi(signal)]
+    fn reset(&self) {
+        self.emit_by_name::<()>("reset", &[]);
+    }
+}
+
+fn main() {}
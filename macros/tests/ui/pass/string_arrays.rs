@@ -0,0 +1,120 @@
+// Test string collections passed as GStrv, GList and GPtrArray
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Tags;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Tags {
+        const NAME: &'static str = "StringArrayTags";
+        type Type = super::Tags;
+    }
+
+    impl ObjectImpl for Tags {}
+}
+
+glib::wrapper! {
+    pub struct Tags(ObjectSubclass<imp::Tags>);
+}
+
+#[ffi_impl]
+impl Tags {
+    // (array zero-terminated=1) (element-type utf8) (transfer none) gchar**
+    fn join(&self, tags: Vec<String>) -> String {
+        tags.join(",")
+    }
+
+    fn contains(&self, tags: &[&str], tag: String) -> bool {
+        tags.contains(&tag.as_str())
+    }
+
+    fn count(&self, tags: Option<&[&str]>) -> u32 {
+        tags.map_or(0, |tags| tags.len() as u32)
+    }
+
+    fn first(&self, tags: Vec<&str>) -> String {
+        tags.first().copied().unwrap_or_default().to_string()
+    }
+
+    fn count_strv(&self, tags: glib::StrV) -> u32 {
+        tags.len() as u32
+    }
+
+    // NULL is an error, unlike an empty GStrv
+    fn try_count_strv(&self, tags: glib::StrV) -> Result<u32, glib::Error> {
+        Ok(tags.len() as u32)
+    }
+
+    // Returns: (array zero-terminated=1) (element-type utf8) (transfer full)
+    fn list(&self) -> Vec<String> {
+        vec!["a".to_string(), "b".to_string()]
+    }
+
+    fn list_gstrings(&self) -> Vec<glib::GString> {
+        vec![glib::GString::from("a")]
+    }
+
+    fn list_strv(&self) -> glib::StrV {
+        glib::StrV::from(vec!["a".to_string()])
+    }
+
+    // An explicit length instead of the NULL terminator
+    fn count_counted(&self, #[ffi(zero_terminated = false)] tags: &[String]) -> u32 {
+        tags.len() as u32
+    }
+
+    // GList and GPtrArray on request
+    fn join_list(&self, #[ffi(collection = list)] tags: Vec<String>) -> String {
+        tags.join(",")
+    }
+
+    #[ffi(collection = list)]
+    fn to_list(&self) -> Vec<String> {
+        vec!["a".to_string()]
+    }
+
+    #[ffi(collection = ptr_array)]
+    fn to_ptr_array(&self) -> Vec<String> {
+        vec!["a".to_string()]
+    }
+
+    fn take_ptr_array(
+        &self,
+        #[ffi(collection = ptr_array, transfer = full)] tags: Vec<String>,
+    ) -> u32 {
+        tags.len() as u32
+    }
+
+    // GStrv through out parameters and tuples
+    fn fill(&self, tags: &mut Vec<String>) {
+        tags.push("tag".to_string());
+    }
+
+    #[ffi(out_names(tags, count))]
+    fn tags_and_count(&self) -> (Vec<String>, u32) {
+        (vec!["a".to_string()], 1)
+    }
+
+    async fn fetch(&self) -> Result<Vec<String>, glib::Error> {
+        Ok(vec!["a".to_string()])
+    }
+}
+
+fn main() {
+    let tags = glib::Object::new::<Tags>();
+    let ptr = glib::object::ObjectType::as_ptr(&tags) as *mut _;
+    let empty = glib::StrV::new();
+    let mut error = std::ptr::null_mut();
+    unsafe {
+        let count = ffi::tags_try_count_strv(ptr, empty.as_ptr() as *mut _, &mut error);
+        assert_eq!(count, 0);
+        assert!(error.is_null());
+
+        ffi::tags_try_count_strv(ptr, std::ptr::null_mut(), &mut error);
+        assert!(!error.is_null());
+        glib::ffi::g_error_free(error);
+    }
+}
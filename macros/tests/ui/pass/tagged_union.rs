@@ -0,0 +1,45 @@
+// Test FFI generation for boxed Rust enums exported as tagged unions
+use gobject_macros::ffi_impl;
+
+#[derive(Clone, Debug, PartialEq, glib::Boxed)]
+#[boxed_type(name = "MyShape")]
+pub enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Labeled { label: String, sides: u32 },
+    Empty,
+}
+
+#[ffi_impl(
+    prefix = "my",
+    ty = "boxed",
+    variants(
+        /// A circle with its radius
+        Circle(f64),
+        /// A rectangle with its width and height
+        Rect(f64, f64),
+        Labeled { label: String, sides: u32 },
+        Empty,
+    )
+)]
+impl Shape {
+    fn area(&self) -> f64 {
+        match self {
+            Shape::Circle(radius) => std::f64::consts::PI * radius * radius,
+            Shape::Rect(width, height) => width * height,
+            Shape::Labeled { .. } | Shape::Empty => 0.0,
+        }
+    }
+}
+
+fn main() {
+    use ffi::{MyShape, MyShapeKind};
+
+    let rect = Shape::Rect(2.0, 3.0);
+    let rect_ptr: MyShape = glib::translate::ToGlibPtr::to_glib_none(&rect).0;
+    unsafe {
+        assert_eq!(ffi::my_shape_get_kind(rect_ptr), MyShapeKind::Rect);
+        // Like `g_return_val_if_fail()`, with the first variant
+        assert_eq!(ffi::my_shape_get_kind(std::ptr::null_mut()), MyShapeKind::Circle);
+    }
+}
@@ -0,0 +1,77 @@
+// Test Duration, SystemTime and glib::DateTime conversions
+use std::time::{Duration, SystemTime};
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Scheduler;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Scheduler {
+        const NAME: &'static str = "TimeScheduler";
+        type Type = super::Scheduler;
+    }
+
+    impl ObjectImpl for Scheduler {}
+}
+
+glib::wrapper! {
+    pub struct Scheduler(ObjectSubclass<imp::Scheduler>);
+}
+
+#[ffi_impl]
+impl Scheduler {
+    // gint64 microseconds
+    fn delay(&self, base: Duration, extra: Option<Duration>) -> Duration {
+        base + extra.unwrap_or_default()
+    }
+
+    // guint milliseconds, G_MAXUINT is None
+    fn set_timeout(&self, #[ffi(duration = millis)] timeout: Option<Duration>) -> bool {
+        timeout.is_some()
+    }
+
+    #[ffi(duration = millis)]
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    // gint64 microseconds since the Unix epoch
+    fn deadline(&self, start: SystemTime, timeout: Duration) -> SystemTime {
+        start + timeout
+    }
+
+    fn last_run(&self) -> Option<SystemTime> {
+        None
+    }
+
+    fn elapsed(&self, since: SystemTime, elapsed: &mut Duration) -> bool {
+        *elapsed = since.elapsed().unwrap_or_default();
+        true
+    }
+
+    fn window(&self) -> (SystemTime, Duration) {
+        (SystemTime::UNIX_EPOCH, Duration::from_secs(1))
+    }
+
+    // GDateTime*
+    fn next_run(&self, after: &glib::DateTime) -> Result<glib::DateTime, glib::Error> {
+        after
+            .add_hours(1)
+            .map_err(|e| glib::Error::new(glib::FileError::Inval, &e.to_string()))
+    }
+
+    async fn wait(&self, #[ffi(duration = millis)] timeout: Duration) -> Duration {
+        timeout
+    }
+
+    async fn schedule(&self, at: glib::DateTime) -> Result<SystemTime, glib::Error> {
+        let _ = at;
+        Ok(SystemTime::now())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+// Test tuple returns mapped to out parameters
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Shape;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Shape {
+        const NAME: &'static str = "TupleShape";
+        type Type = super::Shape;
+    }
+
+    impl ObjectImpl for Shape {}
+}
+
+glib::wrapper! {
+    pub struct Shape(ObjectSubclass<imp::Shape>);
+}
+
+#[ffi_impl]
+impl Shape {
+    // All elements as out parameters: out_x, out_y, out_width, out_height
+    #[ffi(out_names(x, y, width, height))]
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        (0, 0, 10, 20)
+    }
+
+    // Default names: out_0, out_1
+    fn center(&self) -> (f64, f64) {
+        (5.0, 10.0)
+    }
+
+    // First element as the C return value
+    #[ffi(out_names(quotient, remainder), return_first)]
+    fn div_rem(&self, a: i32, b: i32) -> (i32, i32) {
+        (a / b, a % b)
+    }
+
+    // Fallible tuples return a gboolean
+    #[ffi(out_names(name, size))]
+    fn stat(&self, path: String) -> Result<(String, u64), glib::Error> {
+        Ok((path, 42))
+    }
+
+    #[ffi(out_names(name, size), return_first)]
+    fn try_stat(&self, path: String) -> Result<(String, u64), glib::Error> {
+        Ok((path, 42))
+    }
+
+    // Async finish functions get the same out parameters
+    #[ffi(out_names(min, max))]
+    async fn range(&self) -> (i64, i64) {
+        (-1, 1)
+    }
+
+    #[ffi(out_names(text, valid))]
+    async fn try_describe(&self) -> Result<(String, bool), glib::Error> {
+        Ok(("shape".to_string(), true))
+    }
+
+    #[ffi(out_names(count, label), return_first)]
+    async fn summary(&self) -> Result<(u32, String), glib::Error> {
+        Ok((1, "one".to_string()))
+    }
+}
+
+fn main() {}
@@ -0,0 +1,178 @@
+// Test declaring the parent class and implemented interfaces, or reading
+// them from the registered type when left out
+use gio::prelude::*;
+use gio::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+pub mod shape {
+    use super::*;
+
+    mod imp {
+        use super::*;
+        #[derive(Default)]
+        pub struct Shape;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Shape {
+            const NAME: &'static str = "HierarchyShape";
+            type Type = super::Shape;
+            type Class = super::ffi::ShapeClass;
+        }
+
+        impl ObjectImpl for Shape {}
+    }
+
+    glib::wrapper! {
+        pub struct Shape(ObjectSubclass<imp::Shape>);
+    }
+
+    pub trait ShapeImpl: ObjectImpl {}
+
+    unsafe impl<T: ShapeImpl> IsSubclassable<T> for Shape {}
+
+    // C name and header inferred from the `glib` crate
+    #[ffi_impl(parent = glib::Object)]
+    impl Shape {
+        #[ffi(vfunc)]
+        fn sides(&self) -> u32 {
+            0
+        }
+    }
+}
+
+pub mod square {
+    use super::*;
+    use crate::shape::{Shape, ShapeImpl};
+
+    mod imp {
+        use super::*;
+        #[derive(Default)]
+        pub struct Square;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Square {
+            const NAME: &'static str = "HierarchySquare";
+            type Type = super::Square;
+            type ParentType = Shape;
+            type Interfaces = (gio::ListModel,);
+        }
+
+        impl ObjectImpl for Square {}
+        impl ShapeImpl for Square {}
+
+        impl ListModelImpl for Square {
+            fn item_type(&self) -> glib::Type {
+                glib::Object::static_type()
+            }
+
+            fn n_items(&self) -> u32 {
+                0
+            }
+
+            fn item(&self, _position: u32) -> Option<glib::Object> {
+                None
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct Square(ObjectSubclass<imp::Square>)
+            @extends Shape,
+            @implements gio::ListModel;
+    }
+
+    // Types from this crate keep their Rust name unless given `as "CName"`,
+    // and name the header declaring them
+    #[ffi_impl(
+        parent = Shape in "shape.h",
+        implements(gio::ListModel),
+    )]
+    impl Square {
+        fn new() -> Square {
+            glib::Object::new()
+        }
+
+        fn is_empty(&self) -> bool {
+            self.n_items() == 0
+        }
+    }
+}
+
+pub mod window {
+    use super::*;
+
+    mod imp {
+        use super::*;
+        #[derive(Default)]
+        pub struct Window;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Window {
+            const NAME: &'static str = "HierarchyWindow";
+            type Type = super::Window;
+            type ParentType = gio::Application;
+            type Interfaces = (gio::ListModel,);
+        }
+
+        impl ObjectImpl for Window {}
+        impl ApplicationImpl for Window {}
+
+        impl ListModelImpl for Window {
+            fn item_type(&self) -> glib::Type {
+                glib::Object::static_type()
+            }
+
+            fn n_items(&self) -> u32 {
+                0
+            }
+
+            fn item(&self, _position: u32) -> Option<glib::Object> {
+                None
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct Window(ObjectSubclass<imp::Window>)
+            @extends gio::Application,
+            @implements gio::ActionMap, gio::ActionGroup, gio::ListModel;
+    }
+
+    // Neither declared, so nothing is asserted
+    #[ffi_impl]
+    impl Window {
+        fn is_empty(&self) -> bool {
+            self.n_items() == 0
+        }
+    }
+}
+
+fn main() {
+    use gobject_ffi::manifest::TypeManifest;
+
+    let shape = TypeManifest::of::<shape::Shape>();
+    assert_eq!(shape.parent.as_deref(), Some("GObject.Object"));
+    assert!(shape.implements.is_empty());
+
+    let square = TypeManifest::of::<square::Square>();
+    assert_eq!(square.c_type_name, "Square");
+    assert_eq!(square.get_type, "square_get_type");
+    assert_eq!(square.parent.as_deref(), Some("HierarchyShape"));
+    assert_eq!(square.implements, ["Gio.ListModel"]);
+    assert_eq!(
+        square.to_gir(),
+        "<class name=\"Square\" c:type=\"Square\" parent=\"HierarchyShape\" \
+         glib:type-name=\"HierarchySquare\" glib:get-type=\"square_get_type\">\n  \
+         <implements name=\"Gio.ListModel\"/>\n</class>\n"
+    );
+
+    // Interfaces of `GApplication` stay on the parent
+    let window = TypeManifest::of::<window::Window>();
+    assert_eq!(window.parent.as_deref(), Some("Gio.Application"));
+    assert_eq!(window.implements, ["Gio.ListModel"]);
+    assert_eq!(
+        window.to_key_file(),
+        "[Window]\nkind=class\ntype-name=HierarchyWindow\nget-type=window_get_type\n\
+         parent=Gio.Application\nimplements=Gio.ListModel;\n"
+    );
+}
@@ -0,0 +1,88 @@
+// Test plain data structs passed and returned as GVariant*
+use std::collections::HashMap;
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+#[derive(Debug, Clone, PartialEq, glib::Variant)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, glib::Variant)]
+pub struct Shape {
+    pub name: String,
+    pub points: Vec<Point>,
+}
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Canvas;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Canvas {
+        const NAME: &'static str = "VariantCanvas";
+        type Type = super::Canvas;
+    }
+
+    impl ObjectImpl for Canvas {}
+}
+
+glib::wrapper! {
+    pub struct Canvas(ObjectSubclass<imp::Canvas>);
+}
+
+#[ffi_impl]
+impl Canvas {
+    // Checked against the static variant type of `Point`
+    #[ffi(variant)]
+    fn translate(&self, #[ffi(variant)] point: Point, dx: i32, dy: i32) -> Point {
+        Point {
+            x: point.x + dx,
+            y: point.y + dy,
+        }
+    }
+
+    // The type string documents the struct's signature in the header
+    #[ffi(variant = "(sa(ii))")]
+    fn close(&self, #[ffi(variant = "(sa(ii))")] shape: Shape) -> Result<Shape, glib::Error> {
+        let mut shape = shape;
+        if let Some(first) = shape.points.first().cloned() {
+            shape.points.push(first);
+        }
+        Ok(shape)
+    }
+
+    #[ffi(variant)]
+    fn find(&self, #[ffi(variant)] name: Option<String>) -> Option<Shape> {
+        name.map(|name| Shape {
+            name,
+            points: Vec::new(),
+        })
+    }
+
+    // Type strings of std types are known to the header
+    #[ffi(variant)]
+    fn bounds(&self) -> (Point, Point) {
+        (Point { x: 0, y: 0 }, Point { x: 1, y: 1 })
+    }
+
+    #[ffi(variant)]
+    fn labels(&self) -> HashMap<String, u32> {
+        HashMap::new()
+    }
+
+    #[ffi(variant = "a{sv}")]
+    fn state(&self) -> glib::Variant {
+        glib::VariantDict::new(None).end()
+    }
+
+    #[ffi(variant)]
+    async fn load(&self, #[ffi(variant)] point: Point) -> Result<Vec<Point>, glib::Error> {
+        Ok(vec![point])
+    }
+}
+
+fn main() {}
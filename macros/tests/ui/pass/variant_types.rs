@@ -0,0 +1,74 @@
+// Test glib::Value and variant-typed parameters
+use std::collections::HashMap;
+
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Settings;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Settings {
+        const NAME: &'static str = "VariantSettings";
+        type Type = super::Settings;
+    }
+
+    impl ObjectImpl for Settings {}
+}
+
+glib::wrapper! {
+    pub struct Settings(ObjectSubclass<imp::Settings>);
+}
+
+#[ffi_impl]
+impl Settings {
+    // GValue*
+    fn describe(&self, value: &glib::Value) -> glib::Value {
+        value.type_().name().to_value()
+    }
+
+    fn first_value(&self, values: Option<glib::Value>) -> Option<glib::Value> {
+        values
+    }
+
+    // GVariant* checked against the type string before the call
+    fn apply(
+        &self,
+        #[ffi(variant = "a{sv}")] options: glib::Variant,
+    ) -> Result<u32, glib::Error> {
+        Ok(options.n_children() as u32)
+    }
+
+    fn merge(
+        &self,
+        #[ffi(variant = "(sa{sv})")] patch: Option<glib::Variant>,
+    ) -> bool {
+        patch.is_some()
+    }
+
+    // GVariant* of the static variant type of the Rust value
+    fn set_size(&self, #[ffi(variant)] size: (u32, u32)) -> u32 {
+        size.0 * size.1
+    }
+
+    fn set_labels(&self, #[ffi(variant)] labels: Option<HashMap<String, String>>) -> u32 {
+        labels.map_or(0, |labels| labels.len() as u32)
+    }
+
+    fn set_name(&self, #[ffi(variant = "s")] name: String) -> bool {
+        !name.is_empty()
+    }
+
+    async fn apply_later(
+        &self,
+        #[ffi(variant = "a{sv}")] options: glib::Variant,
+    ) -> glib::Value {
+        (options.n_children() as u32).to_value()
+    }
+}
+
+use glib::prelude::*;
+
+fn main() {}
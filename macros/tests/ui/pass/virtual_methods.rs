@@ -0,0 +1,52 @@
+// Test class struct slots for methods C subclasses can override
+use glib::subclass::prelude::*;
+use gobject_macros::ffi_impl;
+
+mod imp {
+    use super::*;
+    #[derive(Default)]
+    pub struct Shape;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Shape {
+        const NAME: &'static str = "VirtualShape";
+        type Type = super::Shape;
+        // Generated by ffi_impl from the `vfunc` methods
+        type Class = super::ffi::ShapeClass;
+    }
+
+    impl ObjectImpl for Shape {}
+}
+
+glib::wrapper! {
+    pub struct Shape(ObjectSubclass<imp::Shape>);
+}
+
+#[ffi_impl]
+impl Shape {
+    fn new() -> Shape {
+        glib::Object::new()
+    }
+
+    #[ffi(vfunc)]
+    fn area(&self, scale: f64) -> f64 {
+        scale
+    }
+
+    #[ffi(vfunc)]
+    fn name(&self) -> String {
+        String::from("shape")
+    }
+
+    #[ffi(vfunc)]
+    fn bounds(&self) -> Result<(i32, i32), glib::Error> {
+        Ok((1, 1))
+    }
+
+    // Not overridable
+    fn describe(&self) -> String {
+        format!("{} of area {}", self.name(), self.area(1.0))
+    }
+}
+
+fn main() {}
@@ -1,29 +1,216 @@
-use glib::subclass::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::OnceLock,
+};
+
+use glib::prelude::*;
+use glib::subclass::{Signal, prelude::*};
 use gobject_macros::ffi_impl;
 
+#[derive(Debug, Clone, Copy, glib::Variant)]
+pub struct Range {
+    pub start: i32,
+    pub end: i32,
+}
+
+type Transform = Box<dyn Fn(i32) -> i32>;
+
 mod imp {
     use super::*;
 
-    #[derive(Default)]
-    pub struct Calculator;
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::Calculator)]
+    pub struct Calculator {
+        pub transform: RefCell<Option<Transform>>,
+        #[property(get, set, maximum = 10)]
+        precision: Cell<u32>,
+        #[property(get, set, construct_only, nullable)]
+        display_name: RefCell<Option<String>>,
+    }
 
     #[glib::object_subclass]
     impl ObjectSubclass for Calculator {
         const NAME: &'static str = "TestCalculator";
         type Type = super::Calculator;
+        type Class = super::ffi::CalculatorClass;
+        type Interfaces = (super::Describable,);
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for Calculator {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| {
+                vec![
+                    Signal::builder("result-ready")
+                        .param_types([i32::static_type()])
+                        .build(),
+                ]
+            })
+        }
+    }
+}
+
+pub mod describable {
+    use super::*;
+
+    mod imp {
+        use super::*;
+
+        pub enum Describable {}
+
+        #[glib::object_interface]
+        impl ObjectInterface for Describable {
+            const NAME: &'static str = "TestDescribable";
+            type Interface = super::ffi::DescribableInterface;
+        }
+    }
+
+    glib::wrapper! {
+        pub struct Describable(ObjectInterface<imp::Describable>);
     }
 
-    impl ObjectImpl for Calculator {}
+    unsafe impl<T: ObjectSubclass> IsImplementable<T> for Describable {}
+
+    #[ffi_impl(ty = "interface", generate_header = "describable.h")]
+    impl Describable {
+        #[ffi(vfunc)]
+        fn describe(&self) -> String {
+            format!("a {}", self.type_().name())
+        }
+    }
 }
 
+pub use describable::Describable;
+
+pub mod point {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    #[ffi_impl(
+        ty = "struct",
+        generate_header = "point.h",
+        fields(
+            /// Horizontal coordinate
+            x: f64,
+            /// Vertical coordinate
+            y: f64,
+        )
+    )]
+    impl Point {
+        fn new(x: f64, y: f64) -> Point {
+            Point { x, y }
+        }
+
+        fn distance(&self, other: &Point) -> f64 {
+            ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+        }
+
+        fn translate(&mut self, dx: f64, dy: f64) {
+            self.x += dx;
+            self.y += dy;
+        }
+
+        fn scaled(&self, factor: f64) -> Self {
+            Point {
+                x: self.x * factor,
+                y: self.y * factor,
+            }
+        }
+    }
+}
+
+pub use point::Point;
+
+pub mod shape {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, glib::Boxed)]
+    #[boxed_type(name = "Shape")]
+    pub enum Shape {
+        Circle(f64),
+        Rect { width: f64, height: f64 },
+        Named(String),
+        Empty,
+    }
+
+    #[ffi_impl(
+        ty = "boxed",
+        generate_header = "shape.h",
+        variants(
+            /// A circle with its radius
+            Circle(f64),
+            /// A rectangle
+            Rect { width: f64, height: f64 },
+            /// A shape known by name only
+            Named(String),
+            /// No shape at all
+            Empty,
+        )
+    )]
+    impl Shape {
+        fn area(&self) -> f64 {
+            match self {
+                Shape::Circle(radius) => std::f64::consts::PI * radius * radius,
+                Shape::Rect { width, height } => width * height,
+                Shape::Named(_) | Shape::Empty => 0.0,
+            }
+        }
+    }
+}
+
+pub use shape::Shape;
+
+pub mod interval {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    pub struct IntervalData {
+        pub start: i64,
+        pub end: i64,
+    }
+
+    #[derive(Clone, Debug, glib::SharedBoxed)]
+    #[shared_boxed_type(name = "Interval")]
+    pub struct Interval(Arc<IntervalData>);
+
+    #[ffi_impl(ty = "shared", generate_header = "interval.h")]
+    impl Interval {
+        fn new(start: i64, end: i64) -> Interval {
+            Interval(Arc::new(IntervalData { start, end }))
+        }
+
+        fn length(&self) -> i64 {
+            self.0.end - self.0.start
+        }
+    }
+}
+
+pub use interval::Interval;
+
 glib::wrapper! {
-    pub struct Calculator(ObjectSubclass<imp::Calculator>);
+    pub struct Calculator(ObjectSubclass<imp::Calculator>) @implements Describable;
 }
 
 unsafe impl Sync for Calculator {}
 unsafe impl Send for Calculator {}
 
-#[ffi_impl(generate_header = "calculator.h")]
+#[ffi_impl(
+    generate_header = "calculator.h",
+    implements(Describable in "describable.h"),
+    properties(
+        #[property(get, set)] precision: u32,
+        #[property(get, set, construct_only)] display_name: Option<String>,
+    )
+)]
 impl Calculator {
     fn new() -> Self {
         glib::Object::new()
@@ -49,6 +236,11 @@ impl Calculator {
         -value
     }
 
+    #[ffi(vfunc)]
+    fn adjust(&self, value: i32) -> i32 {
+        value + 1
+    }
+
     fn divide(&self, a: i32, b: i32) -> Result<i32, glib::Error> {
         if b == 0 {
             Err(glib::Error::new(
@@ -60,15 +252,195 @@ impl Calculator {
         }
     }
 
+    #[ffi(error_value = i32::MIN)]
+    fn checked_subtract(&self, a: i32, b: i32) -> Result<i32, glib::Error> {
+        a.checked_sub(b)
+            .ok_or_else(|| glib::Error::new(glib::FileError::Failed, "Subtraction overflow"))
+    }
+
     fn add_optional(&self, a: i32, b: Option<i32>) -> i32 {
         a + b.unwrap_or(0)
     }
 
+    fn checked_divide(&self, a: i32, b: i32) -> Option<i32> {
+        a.checked_div(b)
+    }
+
     fn compute_sum_and_product(&self, a: i32, b: i32, product: &mut i32) -> i32 {
         *product = a * b;
         a + b
     }
 
+    fn accumulate(&self, total: &mut i32, value: i32) {
+        *total += value;
+    }
+
+    fn describe_sum(&self, a: i32, b: i32, description: &mut String) -> i32 {
+        *description = format!("{} + {} = {}", a, b, a + b);
+        a + b
+    }
+
+    fn append_result(&self, a: i32, b: i32, #[ffi(inout)] log: &mut String) {
+        log.push_str(&format!("{};", a + b));
+    }
+
+    fn sum_if_positive(&self, a: i32, b: i32, error_message: Option<&mut String>) -> bool {
+        if a + b > 0 {
+            return true;
+        }
+        if let Some(message) = error_message {
+            *message = format!("{} is not positive", a + b);
+        }
+        false
+    }
+
+    #[ffi(out_names(quotient, remainder))]
+    fn div_rem(&self, a: i32, b: i32) -> (i32, i32) {
+        (a / b, a % b)
+    }
+
+    #[ffi(out_names(min, max), return_first)]
+    fn min_max(&self, a: i32, b: i32) -> (i32, i32) {
+        (a.min(b), a.max(b))
+    }
+
+    #[ffi(out_names(description, magnitude))]
+    fn describe(&self, value: i32) -> Result<(String, u64), glib::Error> {
+        if value == 0 {
+            Err(glib::Error::new(
+                glib::FileError::Failed,
+                "Zero has no sign",
+            ))
+        } else if value > 0 {
+            Ok(("positive".to_string(), value.unsigned_abs() as u64))
+        } else {
+            Ok(("negative".to_string(), value.unsigned_abs() as u64))
+        }
+    }
+
+    fn sum_all(&self, values: &[i32]) -> i64 {
+        values.iter().map(|&value| value as i64).sum()
+    }
+
+    fn multiples(&self, value: i32, count: u32) -> Vec<i32> {
+        (1..=count as i32).map(|i| value * i).collect()
+    }
+
+    fn format_all(&self, values: &[i32]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[ffi(transfer = none)]
+    fn cached_multiples(&self, value: i32, count: u32) -> Vec<i32> {
+        self.multiples(value, count)
+    }
+
+    #[ffi(transfer = container)]
+    fn format_all_container(&self, values: &[i32]) -> Vec<String> {
+        self.format_all(values)
+    }
+
+    fn parse_all(&self, values: &[&str]) -> i32 {
+        values
+            .iter()
+            .filter_map(|value| value.parse::<i32>().ok())
+            .sum()
+    }
+
+    fn sum_values(&self, values: HashMap<String, i32>) -> i32 {
+        values.values().sum()
+    }
+
+    fn describe_all(&self, a: i32, b: i32) -> HashMap<String, String> {
+        HashMap::from([
+            ("sum".to_string(), (a + b).to_string()),
+            ("product".to_string(), (a * b).to_string()),
+        ])
+    }
+
+    fn parse_number(&self, text: &str) -> Result<i32, glib::Error> {
+        text.trim()
+            .parse()
+            .map_err(|_| glib::Error::new(glib::FileError::Inval, &format!("Not a number: {text}")))
+    }
+
+    fn checksum(&self, data: &[u8]) -> u32 {
+        data.iter().map(|&byte| byte as u32).sum()
+    }
+
+    fn repeat_bytes(&self, data: &glib::Bytes, count: u32) -> glib::Bytes {
+        glib::Bytes::from_owned(data.repeat(count as usize))
+    }
+
+    fn le_bytes(&self, value: u32) -> Vec<u8> {
+        value.to_le_bytes().to_vec()
+    }
+
+    fn basename(&self, path: &std::path::Path) -> Option<std::ffi::OsString> {
+        path.file_name().map(ToOwned::to_owned)
+    }
+
+    fn duplicate_fd(&self, fd: std::os::fd::BorrowedFd<'_>) -> Option<std::os::fd::OwnedFd> {
+        fd.try_clone_to_owned().ok()
+    }
+
+    #[ffi(duration = millis)]
+    fn scale_timeout(
+        &self,
+        #[ffi(duration = millis)] timeout: std::time::Duration,
+        factor: u32,
+    ) -> std::time::Duration {
+        timeout * factor
+    }
+
+    fn time_since(
+        &self,
+        start: std::time::SystemTime,
+        end: std::time::SystemTime,
+    ) -> std::time::Duration {
+        end.duration_since(start).unwrap_or_default()
+    }
+
+    fn sum_options(
+        &self,
+        #[ffi(variant = "a{si}")] options: glib::Variant,
+    ) -> Result<i32, glib::Error> {
+        let options: HashMap<String, i32> = options.get().unwrap_or_default();
+        Ok(options.values().sum())
+    }
+
+    #[ffi(variant = "(ii)")]
+    fn clamp_range(&self, #[ffi(variant = "(ii)")] range: Range, max: i32) -> Range {
+        Range {
+            start: range.start.min(max),
+            end: range.end.min(max),
+        }
+    }
+
+    fn count_while(&self, limit: i32, f: impl Fn(i32) -> bool) -> i32 {
+        (0..limit).take_while(|&value| f(value)).count() as i32
+    }
+
+    fn set_transform(&self, transform: Option<Box<dyn Fn(i32) -> i32>>) {
+        self.imp().transform.replace(transform);
+    }
+
+    fn transform(&self, value: i32) -> i32 {
+        match *self.imp().transform.borrow() {
+            Some(ref transform) => transform(value),
+            None => value,
+        }
+    }
+
+    #[ffi(signal)]
+    fn result_ready(&self, value: i32) {
+        self.emit_by_name::<()>("result-ready", &[&value]);
+    }
+
+    fn value_to_string(&self, value: &glib::Value) -> Option<String> {
+        value.transform::<String>().ok()?.get().ok()
+    }
+
     async fn compute_factorial(&self, n: u32) -> u64 {
         let mut result = 1u64;
         for i in 1..=n {
@@ -1,5 +1,9 @@
 #include <glib.h>
+#include <glib/gstdio.h>
 #include "calculator.h"
+#include "interval.h"
+#include "point.h"
+#include "shape.h"
 
 static void
 test_constructor (void)
@@ -69,13 +73,46 @@ test_fallible_method_error (void)
     g_object_unref (calc);
 }
 
+static void
+test_fallible_method_error_value (void)
+{
+    Calculator *calc = calculator_new ();
+    GError *error = NULL;
+
+    g_assert_cmpint (calculator_checked_subtract (calc, 0, 1, &error), ==, -1);
+    g_assert_no_error (error);
+
+    g_assert_cmpint (calculator_checked_subtract (calc, G_MININT32, 1, &error), ==, G_MININT32);
+    g_assert_error (error, G_FILE_ERROR, G_FILE_ERROR_FAILED);
+
+    g_error_free (error);
+    g_object_unref (calc);
+}
+
 static void
 test_optional_primitive_parameter (void)
 {
     Calculator *calc = calculator_new ();
+    gint32 three = 3;
+    gint32 minus_one = -1;
 
-    g_assert_cmpint (calculator_add_optional (calc, 5, 3), ==, 8);
-    g_assert_cmpint (calculator_add_optional (calc, 5, 0), ==, 5);
+    g_assert_cmpint (calculator_add_optional (calc, 5, &three), ==, 8);
+    g_assert_cmpint (calculator_add_optional (calc, 5, NULL), ==, 5);
+    g_assert_cmpint (calculator_add_optional (calc, 5, &minus_one), ==, 4);
+
+    g_object_unref (calc);
+}
+
+static void
+test_optional_primitive_return (void)
+{
+    Calculator *calc = calculator_new ();
+    gint32 value = 0;
+
+    g_assert_true (calculator_checked_divide (calc, -10, 10, &value));
+    g_assert_cmpint (value, ==, -1);
+    g_assert_false (calculator_checked_divide (calc, 10, 0, &value));
+    g_assert_true (calculator_checked_divide (calc, 10, 5, NULL));
 
     g_object_unref (calc);
 }
@@ -90,6 +127,622 @@ test_out_parameter (void)
     g_assert_cmpint (sum, ==, 9);
     g_assert_cmpint (product, ==, 20);
 
+    /* Numbers are (inout): the caller's value is read first */
+    product = 5;
+    calculator_accumulate (calc, &product, 3);
+    g_assert_cmpint (product, ==, 8);
+
+    g_object_unref (calc);
+}
+
+static void
+test_string_out_parameter (void)
+{
+    Calculator *calc = calculator_new ();
+    gchar *description = NULL;
+
+    g_assert_cmpint (calculator_describe_sum (calc, 2, 3, &description), ==, 5);
+    g_assert_cmpstr (description, ==, "2 + 3 = 5");
+    g_free (description);
+
+    g_assert_cmpint (calculator_describe_sum (calc, 2, 3, NULL), ==, 5);
+
+    g_object_unref (calc);
+}
+
+static void
+test_optional_out_parameter (void)
+{
+    Calculator *calc = calculator_new ();
+    gchar *message = NULL;
+
+    g_assert_true (calculator_sum_if_positive (calc, 2, 3, &message));
+    g_assert_null (message);
+
+    g_assert_false (calculator_sum_if_positive (calc, 2, -3, &message));
+    g_assert_cmpstr (message, ==, "-1 is not positive");
+    g_free (message);
+
+    g_assert_false (calculator_sum_if_positive (calc, 2, -3, NULL));
+
+    g_object_unref (calc);
+}
+
+static void
+test_string_inout_parameter (void)
+{
+    Calculator *calc = calculator_new ();
+    gchar *log = g_strdup ("");
+
+    calculator_append_result (calc, 1, 2, &log);
+    calculator_append_result (calc, 3, 4, &log);
+    g_assert_cmpstr (log, ==, "3;7;");
+
+    g_free (log);
+    g_object_unref (calc);
+}
+
+static void
+test_tuple_out_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    gint32 quotient = 0;
+    gint32 remainder = 0;
+    gint32 max = 0;
+
+    calculator_div_rem (calc, 17, 5, &quotient, &remainder);
+    g_assert_cmpint (quotient, ==, 3);
+    g_assert_cmpint (remainder, ==, 2);
+
+    calculator_div_rem (calc, 17, 5, NULL, &remainder);
+    g_assert_cmpint (remainder, ==, 2);
+
+    g_assert_cmpint (calculator_min_max (calc, 8, -3, &max), ==, -3);
+    g_assert_cmpint (max, ==, 8);
+
+    g_object_unref (calc);
+}
+
+static void
+test_array_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    const gint32 values[] = { 1, 2, 3, 4 };
+    gint32 *multiples;
+    gsize n_multiples = 0;
+
+    g_assert_cmpint (calculator_sum_all (calc, values, G_N_ELEMENTS (values)), ==, 10);
+    g_assert_cmpint (calculator_sum_all (calc, NULL, 0), ==, 0);
+
+    multiples = calculator_multiples (calc, 3, 4, &n_multiples);
+    g_assert_cmpuint (n_multiples, ==, 4);
+    g_assert_cmpint (multiples[0], ==, 3);
+    g_assert_cmpint (multiples[3], ==, 12);
+    g_free (multiples);
+
+    g_object_unref (calc);
+}
+
+static void
+test_returned_array_transfer (void)
+{
+    Calculator *calc = calculator_new ();
+    const gint32 values[] = { 1, 22 };
+    const gint32 *cached;
+    gchar **formatted;
+    gsize n_cached = 0;
+
+    /* (transfer none): owned by calc until the next call */
+    cached = calculator_cached_multiples (calc, 2, 3, &n_cached);
+    g_assert_cmpuint (n_cached, ==, 3);
+    g_assert_cmpint (cached[2], ==, 6);
+    cached = calculator_cached_multiples (calc, 5, 1, &n_cached);
+    g_assert_cmpuint (n_cached, ==, 1);
+    g_assert_cmpint (cached[0], ==, 5);
+
+    /* (transfer container): free the array, not the strings */
+    formatted = calculator_format_all_container (calc, values, G_N_ELEMENTS (values));
+    g_assert_cmpuint (g_strv_length (formatted), ==, 2);
+    g_assert_cmpstr (formatted[1], ==, "22");
+    g_free (formatted);
+
+    g_object_unref (calc);
+}
+
+static void
+test_string_array_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    const gint32 values[] = { 1, 22, 333 };
+    const gchar *numbers[] = { "1", "two", "3", NULL };
+    g_auto(GStrv) formatted = NULL;
+
+    formatted = calculator_format_all (calc, values, G_N_ELEMENTS (values));
+    g_assert_cmpuint (g_strv_length (formatted), ==, 3);
+    g_assert_cmpstr (formatted[1], ==, "22");
+
+    g_assert_cmpint (calculator_parse_all (calc, (gchar **) numbers), ==, 4);
+    g_assert_cmpint (calculator_parse_all (calc, formatted), ==, 356);
+
+    g_object_unref (calc);
+}
+
+static void
+test_hash_table_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    g_autoptr(GHashTable) values = g_hash_table_new (g_str_hash, g_str_equal);
+    g_autoptr(GHashTable) descriptions = NULL;
+
+    g_hash_table_insert (values, "a", GINT_TO_POINTER (2));
+    g_hash_table_insert (values, "b", GINT_TO_POINTER (5));
+    g_assert_cmpint (calculator_sum_values (calc, values), ==, 7);
+
+    descriptions = calculator_describe_all (calc, 3, 4);
+    g_assert_cmpuint (g_hash_table_size (descriptions), ==, 2);
+    g_assert_cmpstr (g_hash_table_lookup (descriptions, "sum"), ==, "7");
+    g_assert_cmpstr (g_hash_table_lookup (descriptions, "product"), ==, "12");
+
+    g_object_unref (calc);
+}
+
+static void
+test_borrowed_string_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    GError *error = NULL;
+
+    g_assert_cmpint (calculator_parse_number (calc, " 42 ", &error), ==, 42);
+    g_assert_no_error (error);
+
+    calculator_parse_number (calc, "4\xff", &error);
+    g_assert_error (error, G_CONVERT_ERROR, G_CONVERT_ERROR_ILLEGAL_SEQUENCE);
+    g_clear_error (&error);
+
+    calculator_parse_number (calc, "forty-two", &error);
+    g_assert_error (error, G_FILE_ERROR, G_FILE_ERROR_INVAL);
+
+    g_error_free (error);
+    g_object_unref (calc);
+}
+
+static void
+test_byte_buffer_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    const guint8 data[] = { 1, 2, 3, 250 };
+    g_autoptr(GBytes) bytes = g_bytes_new_static ("ab", 2);
+    g_autoptr(GBytes) repeated = NULL;
+    g_autoptr(GBytes) encoded = NULL;
+    gsize size = 0;
+
+    g_assert_cmpuint (calculator_checksum (calc, data, G_N_ELEMENTS (data)), ==, 256);
+    g_assert_cmpuint (calculator_checksum (calc, NULL, 0), ==, 0);
+
+    repeated = calculator_repeat_bytes (calc, bytes, 3);
+    g_assert_cmpmem (g_bytes_get_data (repeated, &size), size, "ababab", 6);
+
+    encoded = calculator_le_bytes (calc, 0x04030201);
+    g_assert_cmpmem (g_bytes_get_data (encoded, &size), size, "\x01\x02\x03\x04", 4);
+
+    g_object_unref (calc);
+}
+
+static void
+test_filename_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    g_autofree gchar *name = NULL;
+
+    /* Filenames are passed byte for byte, even when they are not UTF-8 */
+    name = calculator_basename (calc, "/tmp/caf\xe9.txt");
+    g_assert_cmpstr (name, ==, "caf\xe9.txt");
+    g_assert_null (calculator_basename (calc, "/"));
+
+    g_object_unref (calc);
+}
+
+static void
+test_file_descriptor_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    g_autofree gchar *path = NULL;
+    GError *error = NULL;
+    int fd;
+    int copy;
+
+    fd = g_file_open_tmp (NULL, &path, &error);
+    g_assert_no_error (error);
+
+    /* The borrowed descriptor stays ours, the duplicate is ours to close */
+    copy = calculator_duplicate_fd (calc, fd);
+    g_assert_cmpint (copy, >=, 0);
+    g_assert_cmpint (copy, !=, fd);
+    g_assert_true (g_close (copy, NULL));
+    g_assert_true (g_close (fd, NULL));
+
+    g_unlink (path);
+    g_object_unref (calc);
+}
+
+static void
+test_time_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    gint64 now = g_get_real_time ();
+
+    g_assert_cmpuint (calculator_scale_timeout (calc, 250, 4), ==, 1000);
+    /* Durations too long for a guint saturate */
+    g_assert_cmpuint (calculator_scale_timeout (calc, G_MAXUINT / 2, 4), ==, G_MAXUINT);
+
+    g_assert_cmpint (calculator_time_since (calc, now, now + G_USEC_PER_SEC), ==, G_USEC_PER_SEC);
+    g_assert_cmpint (calculator_time_since (calc, now, now - 1), ==, 0);
+
+    g_object_unref (calc);
+}
+
+static void
+test_variant_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    GError *error = NULL;
+    GVariantBuilder builder;
+    GVariant *options;
+    GVariant *range;
+    gint32 start, end;
+    GValue value = G_VALUE_INIT;
+    gchar *text;
+
+    g_variant_builder_init (&builder, G_VARIANT_TYPE ("a{si}"));
+    g_variant_builder_add (&builder, "{si}", "a", 2);
+    g_variant_builder_add (&builder, "{si}", "b", 3);
+    options = g_variant_ref_sink (g_variant_builder_end (&builder));
+    g_assert_cmpint (calculator_sum_options (calc, options, &error), ==, 5);
+    g_assert_no_error (error);
+    g_variant_unref (options);
+
+    /* Variants of another type are rejected before the call */
+    options = g_variant_ref_sink (g_variant_new_string ("a"));
+    calculator_sum_options (calc, options, &error);
+    g_assert_error (error, G_IO_ERROR, G_IO_ERROR_INVALID_ARGUMENT);
+    g_clear_error (&error);
+    g_variant_unref (options);
+
+    options = g_variant_ref_sink (g_variant_new ("(ii)", 5, 50));
+    range = calculator_clamp_range (calc, options, 10);
+    g_assert_true (g_variant_is_of_type (range, G_VARIANT_TYPE ("(ii)")));
+    g_variant_get (range, "(ii)", &start, &end);
+    g_assert_cmpint (start, ==, 5);
+    g_assert_cmpint (end, ==, 10);
+    g_variant_unref (range);
+    g_variant_unref (options);
+
+    g_value_init (&value, G_TYPE_INT);
+    g_value_set_int (&value, 42);
+    text = calculator_value_to_string (calc, &value);
+    g_assert_cmpstr (text, ==, "42");
+    g_free (text);
+    g_value_unset (&value);
+
+    g_object_unref (calc);
+}
+
+static gboolean
+below_limit (gint32 value, gpointer user_data)
+{
+    return value < GPOINTER_TO_INT (user_data);
+}
+
+static gint32
+add_offset (gint32 value, gpointer user_data)
+{
+    return value + *(gint32 *) user_data;
+}
+
+static void
+test_closure_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    gint32 *offset = g_new (gint32, 1);
+
+    g_assert_cmpint (calculator_count_while (calc, 10, below_limit, GINT_TO_POINTER (4)), ==, 4);
+
+    /* The transform keeps its data until it is replaced */
+    *offset = 100;
+    calculator_set_transform (calc, add_offset, offset, g_free);
+    g_assert_cmpint (calculator_transform (calc, 1), ==, 101);
+    calculator_set_transform (calc, NULL, NULL, NULL);
+    g_assert_cmpint (calculator_transform (calc, 1), ==, 1);
+
+    g_object_unref (calc);
+}
+
+static void
+on_result_ready (Calculator *calc, gint32 value, gpointer user_data)
+{
+    *(gint32 *) user_data += value;
+}
+
+static void
+test_signals (void)
+{
+    Calculator *calc = calculator_new ();
+    gint32 total = 0;
+    gulong handler_id;
+
+    handler_id = calculator_connect_result_ready (calc, on_result_ready, &total, NULL);
+    g_assert_cmpuint (handler_id, >, 0);
+
+    calculator_result_ready (calc, 3);
+    g_signal_emit_by_name (calc, "result-ready", 4);
+    g_assert_cmpint (total, ==, 7);
+
+    g_signal_handler_disconnect (calc, handler_id);
+    calculator_result_ready (calc, 5);
+    g_assert_cmpint (total, ==, 7);
+
+    g_object_unref (calc);
+}
+
+typedef struct {
+    Calculator parent_instance;
+} DoublingCalculator;
+
+typedef struct {
+    CalculatorClass parent_class;
+} DoublingCalculatorClass;
+
+G_DEFINE_TYPE (DoublingCalculator, doubling_calculator, calculator_get_type ())
+
+static gint32
+doubling_calculator_adjust (Calculator *self,
+                            gint32      value)
+{
+    CalculatorClass *parent_class = (CalculatorClass *) doubling_calculator_parent_class;
+
+    return parent_class->adjust (self, value) * 2;
+}
+
+static void
+doubling_calculator_class_init (DoublingCalculatorClass *klass)
+{
+    CalculatorClass *calculator_class = (CalculatorClass *) klass;
+
+    calculator_class->adjust = doubling_calculator_adjust;
+}
+
+static void
+doubling_calculator_init (DoublingCalculator *self)
+{
+}
+
+static void
+test_virtual_methods (void)
+{
+    Calculator *calc = calculator_new ();
+    Calculator *doubling = g_object_new (doubling_calculator_get_type (), NULL);
+
+    g_assert_cmpint (calculator_adjust (calc, 3), ==, 4);
+    g_assert_cmpint (calculator_adjust (doubling, 3), ==, 8);
+    g_assert_true (G_TYPE_CHECK_INSTANCE_TYPE (doubling, calculator_get_type ()));
+
+    g_object_unref (doubling);
+    g_object_unref (calc);
+}
+
+typedef struct {
+    GObject parent_instance;
+} Plaque;
+
+typedef struct {
+    GObjectClass parent_class;
+} PlaqueClass;
+
+static gchar *
+plaque_describe (Describable *self)
+{
+    return g_strdup ("a brass plaque");
+}
+
+static void
+plaque_describable_init (DescribableInterface *iface)
+{
+    iface->describe = plaque_describe;
+}
+
+G_DEFINE_TYPE_WITH_CODE (Plaque, plaque, G_TYPE_OBJECT,
+                         G_IMPLEMENT_INTERFACE (describable_get_type (), plaque_describable_init))
+
+static void
+plaque_class_init (PlaqueClass *klass)
+{
+}
+
+static void
+plaque_init (Plaque *self)
+{
+}
+
+static void
+test_interfaces (void)
+{
+    Calculator *calc = calculator_new ();
+    GObject *plaque = g_object_new (plaque_get_type (), NULL);
+    gchar *description;
+
+    g_assert_true (G_TYPE_IS_INTERFACE (describable_get_type ()));
+    g_assert_true (g_type_is_a (calculator_get_type (), describable_get_type ()));
+
+    description = describable_describe ((Describable *) calc);
+    g_assert_cmpstr (description, ==, "a TestCalculator");
+    g_free (description);
+
+    description = describable_describe ((Describable *) plaque);
+    g_assert_cmpstr (description, ==, "a brass plaque");
+    g_free (description);
+
+    g_object_unref (plaque);
+    g_object_unref (calc);
+}
+
+static void
+test_type_macros (void)
+{
+    g_autoptr (Calculator) calc = calculator_new ();
+    g_autoptr (GObject) plaque = g_object_new (plaque_get_type (), NULL);
+
+    g_assert_true (IS_CALCULATOR (calc));
+    g_assert_true (IS_CALCULATOR_CLASS (CALCULATOR_GET_CLASS (calc)));
+    g_assert_true (CALCULATOR (calc) == calc);
+    g_assert_false (IS_CALCULATOR (plaque));
+    g_assert_true (g_type_is_a (TYPE_CALCULATOR, TYPE_DESCRIBABLE));
+
+    g_assert_true (IS_DESCRIBABLE (plaque));
+    g_assert_true (DESCRIBABLE_GET_IFACE (plaque)->describe != NULL);
+    g_assert_true (DESCRIBABLE_GET_IFACE (calc)->describe != DESCRIBABLE_GET_IFACE (plaque)->describe);
+}
+
+static void
+test_records (void)
+{
+    Point a;
+    Point b = { .x = 3.0, .y = 4.0 };
+    Point scaled;
+    g_autoptr (Point) copy = NULL;
+    Point *boxed;
+
+    point_new (0.0, 0.0, &a);
+    g_assert_cmpfloat (a.x, ==, 0.0);
+    g_assert_cmpfloat (point_distance (&a, &b), ==, 5.0);
+
+    point_translate (&a, 1.0, 2.0);
+    g_assert_cmpfloat (a.x, ==, 1.0);
+    g_assert_cmpfloat (a.y, ==, 2.0);
+
+    point_scaled (&b, 2.0, &scaled);
+    g_assert_cmpfloat (scaled.x, ==, 6.0);
+    g_assert_cmpfloat (scaled.y, ==, 8.0);
+
+    copy = point_copy (&b);
+    g_assert_true (copy != &b);
+    g_assert_cmpmem (copy, sizeof (Point), &b, sizeof (Point));
+
+    g_assert_true (G_TYPE_IS_BOXED (TYPE_POINT));
+    boxed = g_boxed_copy (TYPE_POINT, &scaled);
+    g_assert_cmpfloat (boxed->y, ==, 8.0);
+    g_boxed_free (TYPE_POINT, boxed);
+}
+
+static void
+test_tagged_unions (void)
+{
+    g_autoptr (Shape) circle = shape_new_circle (1.0);
+    g_autoptr (Shape) rect = shape_new_rect (2.0, 3.0);
+    g_autoptr (Shape) named = shape_new_named ("hexagon");
+    g_autoptr (Shape) empty = shape_new_empty ();
+    g_autoptr (Shape) copy = NULL;
+    g_autofree gchar *name = NULL;
+    g_autoptr (GEnumClass) kinds = NULL;
+    gdouble width = 0.0, height = 0.0;
+
+    g_assert_cmpint (shape_get_kind (circle), ==, SHAPE_KIND_CIRCLE);
+    g_assert_cmpint (shape_get_kind (rect), ==, SHAPE_KIND_RECT);
+    g_assert_cmpint (shape_get_kind (empty), ==, SHAPE_KIND_EMPTY);
+
+    g_assert_true (shape_get_rect (rect, &width, &height));
+    g_assert_cmpfloat (width, ==, 2.0);
+    g_assert_cmpfloat (height, ==, 3.0);
+    g_assert_cmpfloat (shape_area (rect), ==, 6.0);
+    g_assert_false (shape_get_rect (circle, &width, NULL));
+    g_assert_cmpfloat (width, ==, 2.0);
+
+    g_assert_true (shape_get_named (named, &name));
+    g_assert_cmpstr (name, ==, "hexagon");
+
+    copy = shape_copy (named);
+    g_assert_cmpint (shape_get_kind (copy), ==, SHAPE_KIND_NAMED);
+
+    kinds = g_type_class_ref (TYPE_SHAPE_KIND);
+    g_assert_cmpstr (g_enum_get_value (kinds, SHAPE_KIND_RECT)->value_nick, ==, "rect");
+}
+
+static void
+test_boxed_lifecycle (void)
+{
+    Shape *named = shape_new_named ("hexagon");
+    Shape *copy = shape_copy (named);
+    g_autofree gchar *name = NULL;
+
+    g_assert_true (G_TYPE_IS_BOXED (shape_get_type ()));
+    g_assert_true (copy != named);
+
+    /* The copy survives the original */
+    shape_free (named);
+    g_assert_cmpint (shape_get_kind (copy), ==, SHAPE_KIND_NAMED);
+    g_assert_true (shape_get_named (copy, &name));
+    g_assert_cmpstr (name, ==, "hexagon");
+    shape_free (copy);
+}
+
+static void
+test_shared_lifecycle (void)
+{
+    Interval *interval = interval_new (2, 5);
+    Interval *ref = interval_ref (interval);
+
+    g_assert_true (G_TYPE_IS_BOXED (interval_get_type ()));
+    g_assert_true (ref == interval);
+
+    /* The reference keeps the value alive */
+    interval_unref (interval);
+    g_assert_cmpint (interval_length (ref), ==, 3);
+    interval_unref (ref);
+}
+
+static void
+test_properties (void)
+{
+    Calculator *calc = g_object_new (calculator_get_type (), "display-name", "Desk", NULL);
+    gchar *display_name;
+    guint precision = 0;
+
+    g_assert_cmpuint (calculator_get_precision (calc), ==, 0);
+    calculator_set_precision (calc, 4);
+    g_assert_cmpuint (calculator_get_precision (calc), ==, 4);
+    g_object_get (calc, "precision", &precision, NULL);
+    g_assert_cmpuint (precision, ==, 4);
+
+    /* Out of range for the ParamSpec: warned about and ignored like g_object_set () */
+    g_test_expect_message ("GLib-GObject", G_LOG_LEVEL_WARNING, "*out of range*");
+    calculator_set_precision (calc, 20);
+    g_test_assert_expected_messages ();
+    g_assert_cmpuint (calculator_get_precision (calc), ==, 4);
+
+    display_name = calculator_get_display_name (calc);
+    g_assert_cmpstr (display_name, ==, "Desk");
+    g_free (display_name);
+
+    g_object_unref (calc);
+}
+
+static void
+test_fallible_tuple_out_parameters (void)
+{
+    Calculator *calc = calculator_new ();
+    GError *error = NULL;
+    gchar *description = NULL;
+    guint64 magnitude = 0;
+
+    g_assert_true (calculator_describe (calc, -7, &description, &magnitude, &error));
+    g_assert_no_error (error);
+    g_assert_cmpstr (description, ==, "negative");
+    g_assert_cmpuint (magnitude, ==, 7);
+    g_free (description);
+
+    g_assert_false (calculator_describe (calc, 0, NULL, NULL, &error));
+    g_assert_error (error, G_FILE_ERROR, G_FILE_ERROR_FAILED);
+
+    g_error_free (error);
     g_object_unref (calc);
 }
 
@@ -237,8 +890,35 @@ main (int   argc,
     g_test_add_func ("/ffi/string_return", test_string_return);
     g_test_add_func ("/ffi/fallible_method/success", test_fallible_method_success);
     g_test_add_func ("/ffi/fallible_method/error", test_fallible_method_error);
+    g_test_add_func ("/ffi/fallible_method/error_value", test_fallible_method_error_value);
     g_test_add_func ("/ffi/optional_primitive_parameter", test_optional_primitive_parameter);
+    g_test_add_func ("/ffi/optional_primitive_return", test_optional_primitive_return);
     g_test_add_func ("/ffi/out_parameter", test_out_parameter);
+    g_test_add_func ("/ffi/string_out_parameter", test_string_out_parameter);
+    g_test_add_func ("/ffi/optional_out_parameter", test_optional_out_parameter);
+    g_test_add_func ("/ffi/string_inout_parameter", test_string_inout_parameter);
+    g_test_add_func ("/ffi/tuple_out_parameters", test_tuple_out_parameters);
+    g_test_add_func ("/ffi/fallible_tuple_out_parameters", test_fallible_tuple_out_parameters);
+    g_test_add_func ("/ffi/array_parameters", test_array_parameters);
+    g_test_add_func ("/ffi/returned_array_transfer", test_returned_array_transfer);
+    g_test_add_func ("/ffi/string_array_parameters", test_string_array_parameters);
+    g_test_add_func ("/ffi/hash_table_parameters", test_hash_table_parameters);
+    g_test_add_func ("/ffi/borrowed_string_parameters", test_borrowed_string_parameters);
+    g_test_add_func ("/ffi/byte_buffer_parameters", test_byte_buffer_parameters);
+    g_test_add_func ("/ffi/filename_parameters", test_filename_parameters);
+    g_test_add_func ("/ffi/file_descriptor_parameters", test_file_descriptor_parameters);
+    g_test_add_func ("/ffi/time_parameters", test_time_parameters);
+    g_test_add_func ("/ffi/variant_parameters", test_variant_parameters);
+    g_test_add_func ("/ffi/closure_parameters", test_closure_parameters);
+    g_test_add_func ("/ffi/signals", test_signals);
+    g_test_add_func ("/ffi/properties", test_properties);
+    g_test_add_func ("/ffi/virtual_methods", test_virtual_methods);
+    g_test_add_func ("/ffi/interfaces", test_interfaces);
+    g_test_add_func ("/ffi/type_macros", test_type_macros);
+    g_test_add_func ("/ffi/records", test_records);
+    g_test_add_func ("/ffi/tagged_unions", test_tagged_unions);
+    g_test_add_func ("/ffi/boxed_lifecycle", test_boxed_lifecycle);
+    g_test_add_func ("/ffi/shared_lifecycle", test_shared_lifecycle);
     g_test_add_func ("/ffi/async_method", test_async_method);
     g_test_add_func ("/ffi/async_sync_wrapper", test_async_sync_wrapper);
     g_test_add_func ("/ffi/fallible_async_method/success", test_fallible_async_method_success);